@@ -0,0 +1,325 @@
+//! Procedural macros for klefki.
+//!
+//! `prime_field!` declares a prime field from its modulus, e.g.
+//!
+//! ```ignore
+//! prime_field!(
+//!     pub BabyJubJubField = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+//!     generator = 5,
+//!     factors = [237073, 405928799, 1670836401704629, 13818364434197438864469338081],
+//! );
+//! ```
+//!
+//! expands to a `BabyJubJubFieldParams` implementing `FpParams` with every
+//! constant precomputed (modulus limbs, Montgomery R, R^2, two-adicity and a
+//! multiplicative generator, -p^(-1) mod 2^64 is left to the `FpParams`
+//! default), the element type
+//! `BabyJubJubFieldEle = Fp<BabyJubJubFieldParams, N>` and the boxed
+//! `BabyJubJubField`. `Property` and `FromBigUint` come with `Fp`.
+//!
+//! The modulus is an integer literal or a string, decimal or `0x` prefixed
+//! hex. Checking a generator needs every prime factor of p - 1: the ones
+//! trial division cannot reach go in `factors`, and the macro fails to
+//! compile when p - 1 is still not fully factored. The generator is optional,
+//! without it the smallest one is searched.
+use num_bigint::BigUint;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Ident, Lit, Token, Visibility};
+
+/// Primes below this bound are found by trial division when factoring p - 1.
+const TRIAL_DIVISION_BOUND: u32 = 1 << 16;
+
+/// Miller-Rabin witnesses, deterministic below 3.3·10^24 and a negligible
+/// error probability above.
+const WITNESSES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+struct PrimeFieldInput {
+    vis: Visibility,
+    name: Ident,
+    modulus: (BigUint, Span),
+    generator: Option<(BigUint, Span)>,
+    factors: Vec<(BigUint, Span)>,
+}
+
+fn parse_digits(s: &str) -> Option<BigUint> {
+    let s = s.replace('_', "");
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        return BigUint::parse_bytes(hex.as_bytes(), 16);
+    }
+    return BigUint::parse_bytes(s.as_bytes(), 10);
+}
+
+/// Integer literal of any size or a decimal/hex string.
+fn parse_number(input: ParseStream) -> syn::Result<(BigUint, Span)> {
+    let lit: Lit = input.parse()?;
+    let span = lit.span();
+    let value = match lit {
+        Lit::Int(i) => {
+            if !i.suffix().is_empty() {
+                return Err(syn::Error::new(span, "expected an unsuffixed integer"));
+            }
+            BigUint::parse_bytes(i.base10_digits().as_bytes(), 10)
+        }
+        Lit::Str(s) => parse_digits(&s.value()),
+        _ => None,
+    };
+    return match value {
+        Some(v) => Ok((v, span)),
+        None => Err(syn::Error::new(span, "expected a decimal or 0x prefixed hex integer")),
+    };
+}
+
+impl Parse for PrimeFieldInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis: Visibility = input.parse()?;
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let modulus = parse_number(input)?;
+        let mut generator = None;
+        let mut factors = Vec::<(BigUint, Span)>::new();
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            if key == "generator" && generator.is_none() {
+                generator = Some(parse_number(input)?);
+            } else if key == "factors" && factors.is_empty() {
+                let content;
+                syn::bracketed!(content in input);
+                while !content.is_empty() {
+                    factors.push(parse_number(&content)?);
+                    content.parse::<Option<Token![,]>>()?;
+                }
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "expected `generator = <integer>` or `factors = [<integer>, ...]`",
+                ));
+            }
+        }
+        return Ok(PrimeFieldInput {
+            vis: vis,
+            name: name,
+            modulus: modulus,
+            generator: generator,
+            factors: factors,
+        });
+    }
+}
+
+/// Miller-Rabin
+fn is_probable_prime(n: &BigUint) -> bool {
+    let one = BigUint::from(1u32);
+    if *n < BigUint::from(2u32) {
+        return false;
+    }
+    for w in WITNESSES.iter() {
+        if *n == BigUint::from(*w) {
+            return true;
+        }
+        if (n % *w) == BigUint::from(0u32) {
+            return false;
+        }
+    }
+    let n1 = n - 1u32;
+    let s = two_adicity(n);
+    let d = n1.clone() >> s;
+    'witness: for w in WITNESSES.iter() {
+        let mut x = BigUint::from(*w).modpow(&d, n);
+        if x == one || x == n1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.clone() * x % n;
+            if x == n1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    return true;
+}
+
+/// Largest s with 2^s | n - 1, n odd
+fn two_adicity(n: &BigUint) -> usize {
+    let mut m = n - 1u32;
+    let mut s = 0;
+    while (m.clone() & BigUint::from(1u32)) == BigUint::from(0u32) {
+        m >>= 1;
+        s += 1;
+    }
+    return s;
+}
+
+/// Distinct prime factors of n found by trial division, and the part of n
+/// they leave unfactored, 1 when they are all of them.
+fn prime_factors(n: &BigUint) -> (Vec<BigUint>, BigUint) {
+    let mut factors = Vec::<BigUint>::new();
+    let mut m = n.clone();
+    let mut d = 2u32;
+    while d < TRIAL_DIVISION_BOUND && BigUint::from(d) * d <= m {
+        if (m.clone() % d) == BigUint::from(0u32) {
+            factors.push(BigUint::from(d));
+            while (m.clone() % d) == BigUint::from(0u32) {
+                m /= d;
+            }
+        }
+        d += if d == 2 { 1 } else { 2 };
+    }
+    if m == BigUint::from(1u32) {
+        return (factors, m);
+    }
+    // what is left is prime once d^2 > m, otherwise it has no factor below the bound
+    if BigUint::from(d) * d > m || is_probable_prime(&m) {
+        factors.push(m);
+        return (factors, BigUint::from(1u32));
+    }
+    return (factors, m);
+}
+
+/// g^((p-1)/q) != 1 for every prime q | p - 1 in `factors`
+fn generates(g: &BigUint, p: &BigUint, factors: &[BigUint]) -> bool {
+    let p1 = p - 1u32;
+    return factors.iter().all(|q| g.modpow(&(p1.clone() / q), p) != BigUint::from(1u32));
+}
+
+/// Little endian u64 limbs, zero padded to n.
+fn limbs(v: &BigUint, n: usize) -> Vec<u64> {
+    let digits = v.to_u32_digits();
+    let mut out = vec![0u64; n];
+    for (i, d) in digits.iter().enumerate() {
+        out[i / 2] |= u64::from(*d) << (32 * (i % 2));
+    }
+    return out;
+}
+
+fn expand(input: PrimeFieldInput) -> syn::Result<proc_macro2::TokenStream> {
+    let (p, p_span) = input.modulus;
+    if p < BigUint::from(3u32) || !is_probable_prime(&p) {
+        return Err(syn::Error::new(p_span, "modulus must be an odd prime"));
+    }
+    let p1 = p.clone() - 1u32;
+    let (mut factors, mut rest) = prime_factors(&p1);
+    for (q, span) in input.factors {
+        if !is_probable_prime(&q) || (p1.clone() % q.clone()) != BigUint::from(0u32) {
+            return Err(syn::Error::new(span, "not a prime factor of p - 1"));
+        }
+        while (rest.clone() % q.clone()) == BigUint::from(0u32) {
+            rest /= q.clone();
+        }
+        if !factors.contains(&q) {
+            factors.push(q);
+        }
+    }
+    if rest != BigUint::from(1u32) && is_probable_prime(&rest) {
+        factors.push(rest);
+        rest = BigUint::from(1u32);
+    }
+    if rest != BigUint::from(1u32) {
+        return Err(syn::Error::new(
+            p_span,
+            format!("p - 1 could not be factored, pass the prime factors of {} as `factors = [...]`", rest),
+        ));
+    }
+    let g = match input.generator {
+        Some((g, span)) => {
+            if g < BigUint::from(2u32) || g >= p || !generates(&g, &p, &factors) {
+                return Err(syn::Error::new(span, "not a generator of the multiplicative group"));
+            }
+            g
+        }
+        None => {
+            let mut g = BigUint::from(2u32);
+            while !generates(&g, &p, &factors) {
+                g += 1u32;
+            }
+            g
+        }
+    };
+
+    let n = ((p.bits() + 63) / 64) as usize;
+    let r = (BigUint::from(1u32) << (64 * n)) % p.clone();
+    let r2 = r.clone() * r.clone() % p.clone();
+    let modulus = limbs(&p, n);
+    let r = limbs(&r, n);
+    let r2 = limbs(&r2, n);
+    let generator = limbs(&g, n);
+    let s = two_adicity(&p) as u32;
+
+    let vis = input.vis;
+    let name = input.name;
+    let params = format_ident!("{}Params", name);
+    let ele = format_ident!("{}Ele", name);
+    let doc = format!("Prime field modulo {}", p);
+    return Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Eq, PartialEq, Clone, Copy)]
+        #vis struct #params;
+
+        impl ::klefki::algebra::fields::fp::FpParams<#n> for #params {
+            const MODULUS: [u64; #n] = [#(#modulus),*];
+            const R: [u64; #n] = [#(#r),*];
+            const R2: [u64; #n] = [#(#r2),*];
+            const TWO_ADICITY: u32 = #s;
+            const GENERATOR: [u64; #n] = [#(#generator),*];
+        }
+
+        #vis type #ele = ::klefki::algebra::fields::fp::Fp<#params, #n>;
+
+        #vis type #name = ::klefki::algebra::fields::prime::BoxedPrimeField<#ele>;
+    });
+}
+
+/// Declares a prime field, see the crate documentation.
+#[proc_macro]
+pub fn prime_field(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as PrimeFieldInput);
+    return match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_digits() {
+        assert_eq!(parse_digits("0x1f"), Some(BigUint::from(31u32)));
+        assert_eq!(parse_digits("1_000"), Some(BigUint::from(1000u32)));
+        assert_eq!(parse_digits("0xg"), None);
+    }
+
+    #[test]
+    fn test_is_probable_prime() {
+        let p = parse_digits("21888242871839275222246405745257275088548364400416034343698204186575808495617").unwrap();
+        let primes = (2u32..200).filter(|n| (2..*n).all(|d| n % d != 0)).collect::<Vec<u32>>();
+        for n in 0u32..200 {
+            assert_eq!(is_probable_prime(&BigUint::from(n)), primes.contains(&n));
+        }
+        assert_eq!(is_probable_prime(&p), true);
+        assert_eq!(is_probable_prime(&(p.clone() * 3u32)), false);
+        // Carmichael number
+        assert_eq!(is_probable_prime(&BigUint::from(561u32)), false);
+    }
+
+    #[test]
+    fn test_generator() {
+        let p = BigUint::from(10007u32);
+        let (factors, rest) = prime_factors(&(p.clone() - 1u32));
+        assert_eq!(rest, BigUint::from(1u32));
+        assert_eq!(factors, vec![BigUint::from(2u32), BigUint::from(5003u32)]);
+        assert_eq!(generates(&BigUint::from(5u32), &p, &factors), true);
+        // a square never generates
+        assert_eq!(generates(&BigUint::from(4u32), &p, &factors), false);
+        assert_eq!(two_adicity(&p), 1);
+        // p - 1 = 2^2 · 11 · 1048583 · 1049599, both large factors past the bound
+        let p = BigUint::from(48426033401549u64);
+        let (factors, rest) = prime_factors(&(p.clone() - 1u32));
+        assert_eq!(factors, vec![BigUint::from(2u32), BigUint::from(11u32)]);
+        assert_eq!(rest, BigUint::from(1048583u64 * 1049599u64));
+    }
+}
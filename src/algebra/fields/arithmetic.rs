@@ -1,6 +1,8 @@
+use crate::algebra::traits::MulInv;
 use num::traits::{One, Zero};
 use num_bigint::BigInt;
 use num_bigint::BigUint;
+use std::ops::Mul;
 
 pub fn extended_euclidean_algorithm(a: BigUint, b: BigUint) -> (BigInt, BigInt, BigInt) {
     let (mut s, mut t, mut r) = (BigInt::zero(), BigInt::one(), BigInt::from(b));
@@ -24,12 +26,53 @@ pub fn extended_euclidean_algorithm(a: BigUint, b: BigUint) -> (BigInt, BigInt,
     return (old_r, old_s, old_t);
 }
 
+/// Montgomery's trick, inverts every element of `v` in place with a single
+/// field inversion and 3(n-1) multiplications.
+/// Zero has no inverse, zero elements are skipped and left as zero.
+/// # ref:
+/// * https://link.springer.com/content/pdf/10.1007/3-540-36400-5_4.pdf
+pub fn batch_inversion<F>(v: &mut [F])
+where
+    F: Clone + Zero + One + Mul<Output = F> + MulInv<Output = F>,
+{
+    // prefix[i] = v[0] * ... * v[i] over the non-zero elements
+    let mut prefix = Vec::<F>::with_capacity(v.len());
+    for x in v.iter() {
+        if x.is_zero() {
+            continue;
+        }
+        prefix.push(match prefix.last() {
+            Some(acc) => acc.clone() * x.clone(),
+            None => x.clone(),
+        });
+    }
+    let mut inv = match prefix.pop() {
+        Some(acc) => acc.mul_inv(),
+        None => return,
+    };
+    for x in v.iter_mut().rev() {
+        if x.is_zero() {
+            continue;
+        }
+        // inv = (v[0] * ... * x)^-1 at this point
+        let x_inv = match prefix.pop() {
+            Some(acc) => inv.clone() * acc,
+            None => inv.clone(),
+        };
+        inv = inv * x.clone();
+        *x = x_inv;
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::algebra::fields::arithmetic::extended_euclidean_algorithm;
+    use crate::algebra::fields::arithmetic::{batch_inversion, extended_euclidean_algorithm};
+    use crate::algebra::traits::MulInv;
+    use crate::curves::baby_jubjub::ff::{BabyJubJubField, BabyJubJubFieldEle};
     use num::traits::{One, Zero};
     use num_bigint::BigInt;
     use num_bigint::BigUint;
+    use rand::Rng;
     #[test]
     fn test_modinv() {
         let a = BigUint::one();
@@ -39,4 +82,39 @@ mod test {
         assert_eq!(s.clone(), BigInt::one());
         assert_eq!(t.clone(), BigInt::zero());
     }
+
+    #[test]
+    fn test_batch_inversion() {
+        let mut rng = rand::thread_rng();
+        let mut v: Vec<BabyJubJubField> = (0..33)
+            .map(|i| {
+                if i % 7 == 3 {
+                    BabyJubJubField::zero()
+                } else {
+                    BabyJubJubField::from(BigUint::from_slice(&rng.gen::<[u32; 8]>()))
+                }
+            })
+            .collect();
+        let expected: Vec<BabyJubJubField> = v
+            .iter()
+            .map(|x| if x.is_zero() { x.clone() } else { x.clone().mul_inv() })
+            .collect();
+        batch_inversion(&mut v);
+        assert_eq!(v == expected, true);
+    }
+
+    #[test]
+    fn test_batch_inversion_edge_cases() {
+        let mut empty = Vec::<BabyJubJubFieldEle>::new();
+        batch_inversion(&mut empty);
+        assert_eq!(empty.is_empty(), true);
+
+        let mut zeros = vec![BabyJubJubFieldEle::zero(); 3];
+        batch_inversion(&mut zeros);
+        assert_eq!(zeros.iter().all(|x| x.is_zero()), true);
+
+        let mut single = vec![BabyJubJubFieldEle::from(2u32)];
+        batch_inversion(&mut single);
+        assert_eq!((single[0] * BabyJubJubFieldEle::from(2u32)).is_one(), true);
+    }
 }
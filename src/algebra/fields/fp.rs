@@ -0,0 +1,739 @@
+use crate::algebra::fields::prime;
+use crate::algebra::groups::arithmetic::square_and_multiply_algorithm;
+use crate::algebra::traits::{ByteEncoding, CheckedInv, ConditionalSwap, Field, Group, MulInv, Pow, Ring};
+use crate::Error;
+use num::traits::Num;
+use num::traits::{One, Zero};
+use num_bigint::BigUint;
+use std::cmp::{Eq, PartialEq};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::Neg;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// Parameters of a prime field whose modulus fits in `N` 64 bit limbs (least significant first).
+/// The defaults derive the constants from the modulus at compile time, `prime_field!`
+/// declares a field with all of them precomputed.
+pub trait FpParams<const N: usize>: 'static + Sized {
+    const MODULUS: [u64; N];
+    /// R = 2^(64N) mod p, the Montgomery form of one
+    const R: [u64; N] = montgomery_r(&Self::MODULUS);
+    /// R^2 mod p, used to convert into Montgomery form
+    const R2: [u64; N] = montgomery_r2(&Self::MODULUS);
+    /// -p^(-1) mod 2^64
+    const INV: u64 = montgomery_inv(Self::MODULUS[0]);
+    /// Largest s with 2^s | p - 1
+    const TWO_ADICITY: u32 = two_adicity(&Self::MODULUS);
+    /// Generator of the multiplicative group, not in Montgomery form.
+    /// Zero, the default, means no generator is known.
+    const GENERATOR: [u64; N] = [0u64; N];
+}
+
+/// Stack allocated prime field element kept in Montgomery form a·R mod p.
+/// Addition, subtraction, negation, multiplication, inversion,
+/// `ConditionalSwap` and `to_bytes_le` run in time independent of the values;
+/// conversions from and to `BigUint`, comparisons and `Debug` do not.
+/// # ref:
+/// * https://www.microsoft.com/en-us/research/wp-content/uploads/1996/01/j37acmon.pdf
+pub struct Fp<P: FpParams<N>, const N: usize>([u64; N], PhantomData<P>);
+
+const fn geq<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] > b[i] {
+            return true;
+        }
+        if a[i] < b[i] {
+            return false;
+        }
+    }
+    return true;
+}
+
+const fn sub_limbs<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], u64) {
+    let mut out = [0u64; N];
+    let mut borrow = 0u64;
+    let mut i = 0;
+    while i < N {
+        let (d, o1) = a[i].overflowing_sub(b[i]);
+        let (d, o2) = d.overflowing_sub(borrow);
+        out[i] = d;
+        borrow = (o1 | o2) as u64;
+        i += 1;
+    }
+    return (out, borrow);
+}
+
+const fn double_mod<const N: usize>(a: &[u64; N], m: &[u64; N]) -> [u64; N] {
+    let mut out = [0u64; N];
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < N {
+        out[i] = (a[i] << 1) | carry;
+        carry = a[i] >> 63;
+        i += 1;
+    }
+    if carry != 0 || geq(&out, m) {
+        out = sub_limbs(&out, m).0;
+    }
+    return out;
+}
+
+pub const fn montgomery_r<const N: usize>(m: &[u64; N]) -> [u64; N] {
+    let mut r = [0u64; N];
+    r[0] = 1;
+    let mut i = 0;
+    while i < 64 * N {
+        r = double_mod(&r, m);
+        i += 1;
+    }
+    return r;
+}
+
+pub const fn montgomery_r2<const N: usize>(m: &[u64; N]) -> [u64; N] {
+    let mut r = montgomery_r(m);
+    let mut i = 0;
+    while i < 64 * N {
+        r = double_mod(&r, m);
+        i += 1;
+    }
+    return r;
+}
+
+pub const fn montgomery_inv(m0: u64) -> u64 {
+    let mut inv = 1u64;
+    let mut i = 0;
+    while i < 63 {
+        inv = inv.wrapping_mul(inv);
+        inv = inv.wrapping_mul(m0);
+        i += 1;
+    }
+    return inv.wrapping_neg();
+}
+
+pub const fn two_adicity<const N: usize>(m: &[u64; N]) -> u32 {
+    let mut one = [0u64; N];
+    one[0] = 1;
+    let m1 = sub_limbs(m, &one).0;
+    let mut s = 0u32;
+    let mut i = 0;
+    while i < N {
+        if m1[i] != 0 {
+            return s + m1[i].trailing_zeros();
+        }
+        s += 64;
+        i += 1;
+    }
+    return s;
+}
+
+/// Byte length of the modulus, the width of the encoding
+const fn byte_len<const N: usize>(m: &[u64; N]) -> usize {
+    let mut i = N;
+    while i > 1 && m[i - 1] == 0 {
+        i -= 1;
+    }
+    let bits = 64 * i - m[i - 1].leading_zeros() as usize;
+    return bits.div_ceil(8);
+}
+
+/// `a` when choice is 1, `b` when it is 0, without branching on choice.
+#[inline(always)]
+fn select_limbs<const N: usize>(a: &[u64; N], b: &[u64; N], choice: u64) -> [u64; N] {
+    let mask = 0u64.wrapping_sub(choice);
+    let mut out = [0u64; N];
+    for i in 0..N {
+        out[i] = (a[i] & mask) | (b[i] & !mask);
+    }
+    return out;
+}
+
+/// a + b·c + carry
+#[inline(always)]
+fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = u128::from(a) + u128::from(b) * u128::from(c) + u128::from(carry);
+    return (t as u64, (t >> 64) as u64);
+}
+
+/// a + b + carry
+#[inline(always)]
+fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = u128::from(a) + u128::from(b) + u128::from(carry);
+    return (t as u64, (t >> 64) as u64);
+}
+
+impl<P: FpParams<N>, const N: usize> Fp<P, N> {
+    /// Montgomery multiplication a·b·R^(-1) mod p (CIOS)
+    #[inline]
+    fn mont_mul(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+        let mut t = [0u64; N];
+        let mut t_n: u64;
+        let mut t_n1 = 0u64;
+        let mut carry: u64;
+        for i in 0..N {
+            carry = 0;
+            for j in 0..N {
+                (t[j], carry) = mac(t[j], a[j], b[i], carry);
+            }
+            (t_n, t_n1) = adc(t_n1, carry, 0);
+
+            let m = t[0].wrapping_mul(P::INV);
+            (_, carry) = mac(t[0], m, P::MODULUS[0], 0);
+            for j in 1..N {
+                (t[j - 1], carry) = mac(t[j], m, P::MODULUS[j], carry);
+            }
+            (t[N - 1], carry) = adc(t_n, carry, 0);
+            t_n1 += carry;
+        }
+        // t < 2p, subtract p unless that borrows out of t_n1
+        let (s, borrow) = sub_limbs(&t, &P::MODULUS);
+        return select_limbs(&s, &t, t_n1 | (borrow ^ 1));
+    }
+
+    /// Strict constructor, rejects values >= p instead of reducing them.
+    /// The boxed counterpart is `prime::FromBigUint::from_canonical`.
+    pub fn from_canonical_biguint(v: BigUint) -> Result<Self, Error> {
+        if v >= Self::limbs_to_biguint(&P::MODULUS) {
+            return Err(Error::NonCanonicalEncoding);
+        }
+        return Ok(Self::from(v));
+    }
+
+    fn from_canonical_limbs(limbs: [u64; N]) -> Self {
+        return Self(Self::mont_mul(&limbs, &P::R2), PhantomData);
+    }
+
+    fn to_canonical_limbs(&self) -> [u64; N] {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        return Self::mont_mul(&self.0, &one);
+    }
+
+    fn limbs_to_biguint(limbs: &[u64; N]) -> BigUint {
+        let mut digits = Vec::<u32>::with_capacity(2 * N);
+        for l in limbs.iter() {
+            digits.push(*l as u32);
+            digits.push((l >> 32) as u32);
+        }
+        return BigUint::new(digits);
+    }
+
+    /// Square and multiply over little endian exponent limbs
+    fn pow_limbs(&self, e: &[u64]) -> Self {
+        let mut ret = Self::one();
+        for l in e.iter().rev() {
+            for i in (0..64).rev() {
+                ret = ret * ret;
+                if (l >> i) & 1 == 1 {
+                    ret = ret * *self;
+                }
+            }
+        }
+        return ret;
+    }
+
+    /// Generator g of the multiplicative group F_p^*, `None` if the params
+    /// do not set `GENERATOR`
+    pub fn multiplicative_generator() -> Option<Self> {
+        if P::GENERATOR == [0u64; N] {
+            return None;
+        }
+        return Some(Self::from_canonical_limbs(P::GENERATOR));
+    }
+
+    /// Primitive 2^s-th root of unity g^((p - 1) / 2^s), s = `TWO_ADICITY`,
+    /// `None` without a generator
+    pub fn two_adic_root_of_unity() -> Option<Self> {
+        let mut one = [0u64; N];
+        one[0] = 1;
+        let e = Self::limbs_to_biguint(&sub_limbs(&P::MODULUS, &one).0) >> P::TWO_ADICITY as usize;
+        return Self::multiplicative_generator().map(|g| g.pow(&e));
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Clone for Fp<P, N> {
+    fn clone(&self) -> Self {
+        return *self;
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Copy for Fp<P, N> {}
+
+impl<P: FpParams<N>, const N: usize> fmt::Debug for Fp<P, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "Fp({})", Self::limbs_to_biguint(&self.to_canonical_limbs()));
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> PartialEq for Fp<P, N> {
+    fn eq(&self, rhs: &Self) -> bool {
+        return self.0 == rhs.0;
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Eq for Fp<P, N> {}
+
+impl<P: FpParams<N>, const N: usize> Hash for Fp<P, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Add for Fp<P, N> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let mut out = [0u64; N];
+        let mut carry = 0u64;
+        for i in 0..N {
+            (out[i], carry) = adc(self.0[i], rhs.0[i], carry);
+        }
+        let (s, borrow) = sub_limbs(&out, &P::MODULUS);
+        return Self(select_limbs(&s, &out, carry | (borrow ^ 1)), PhantomData);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Sub for Fp<P, N> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let (mut out, borrow) = sub_limbs(&self.0, &rhs.0);
+        let mask = 0u64.wrapping_sub(borrow);
+        let mut carry = 0u64;
+        for i in 0..N {
+            (out[i], carry) = adc(out[i], P::MODULUS[i] & mask, carry);
+        }
+        return Self(out, PhantomData);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Neg for Fp<P, N> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        return Self::zero() - self;
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Mul for Fp<P, N> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        return Self(Self::mont_mul(&self.0, &rhs.0), PhantomData);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> MulInv for Fp<P, N> {
+    type Output = Self;
+    /// Fermat's little theorem, a^(p-2)
+    fn mul_inv(self) -> Self {
+        let mut two = [0u64; N];
+        two[0] = 2;
+        return self.pow_limbs(&sub_limbs(&P::MODULUS, &two).0);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> ConditionalSwap for Fp<P, N> {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: u8) {
+        let mask = 0u64.wrapping_sub(u64::from(choice & 1));
+        for i in 0..N {
+            let t = (a.0[i] ^ b.0[i]) & mask;
+            a.0[i] ^= t;
+            b.0[i] ^= t;
+        }
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> CheckedInv for Fp<P, N> {
+    fn checked_inv(&self) -> Result<Self, Error> {
+        if self.is_zero() {
+            return Err(Error::NonInvertible);
+        }
+        return Ok(self.mul_inv());
+    }
+
+    fn checked_div(&self, rhs: &Self) -> Result<Self, Error> {
+        return Ok(*self * rhs.checked_inv()?);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Pow for Fp<P, N> {
+    fn pow(&self, e: &BigUint) -> Self {
+        return square_and_multiply_algorithm(e.clone(), *self);
+    }
+
+    fn pow_u64(&self, e: u64) -> Self {
+        return self.pow_limbs(&[e]);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Div for Fp<P, N> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        return self * rhs.mul_inv();
+    }
+}
+
+/// Division in a field is exact, so the remainder is always zero.
+impl<P: FpParams<N>, const N: usize> Rem for Fp<P, N> {
+    type Output = Self;
+    fn rem(self, _rhs: Self) -> Self {
+        return Self::zero();
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Zero for Fp<P, N> {
+    fn zero() -> Self {
+        return Self([0u64; N], PhantomData);
+    }
+    fn is_zero(&self) -> bool {
+        return self.0 == [0u64; N];
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> One for Fp<P, N> {
+    fn one() -> Self {
+        return Self(P::R, PhantomData);
+    }
+    fn is_one(&self) -> bool {
+        return self.0 == P::R;
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Num for Fp<P, N> {
+    type FromStrRadixErr = <BigUint as Num>::FromStrRadixErr;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        return Ok(Self::from(BigUint::from_str_radix(s, radix)?));
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> Group for Fp<P, N> {}
+impl<P: FpParams<N>, const N: usize> Ring for Fp<P, N> {}
+impl<P: FpParams<N>, const N: usize> Field for Fp<P, N> {}
+
+impl<P: FpParams<N>, const N: usize> From<BigUint> for Fp<P, N> {
+    fn from(v: BigUint) -> Self {
+        let v = v % Self::limbs_to_biguint(&P::MODULUS);
+        let mut limbs = [0u64; N];
+        for (i, d) in v.to_u32_digits().iter().enumerate() {
+            limbs[i / 2] |= u64::from(*d) << (32 * (i % 2));
+        }
+        return Self::from_canonical_limbs(limbs);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> From<u64> for Fp<P, N> {
+    /// Skips `BigUint`, a single limb only needs reducing when p itself fits in one.
+    fn from(v: u64) -> Self {
+        let mut limbs = [0u64; N];
+        limbs[0] = if N == 1 { v % P::MODULUS[0] } else { v };
+        return Self::from_canonical_limbs(limbs);
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> From<u32> for Fp<P, N> {
+    fn from(v: u32) -> Self {
+        return Self::from(u64::from(v));
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> From<u16> for Fp<P, N> {
+    fn from(v: u16) -> Self {
+        return Self::from(u64::from(v));
+    }
+}
+
+impl<P: FpParams<N>, const N: usize> prime::Property<Fp<P, N>> for Fp<P, N> {
+    fn prime(&self) -> BigUint {
+        return Self::limbs_to_biguint(&P::MODULUS);
+    }
+    fn value(&self) -> BigUint {
+        return Self::limbs_to_biguint(&self.to_canonical_limbs());
+    }
+    fn element(&self) -> Fp<P, N> {
+        return *self;
+    }
+}
+
+/// Little endian bytes of the canonical limbs, without going through `BigUint`.
+impl<P: FpParams<N>, const N: usize> ByteEncoding for Fp<P, N> {
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = Vec::<u8>::with_capacity(8 * N);
+        for l in self.to_canonical_limbs().iter() {
+            bytes.extend_from_slice(&l.to_le_bytes());
+        }
+        bytes.truncate(byte_len(&P::MODULUS));
+        return bytes;
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, Error> {
+        let expected = byte_len(&P::MODULUS);
+        if bytes.len() != expected {
+            return Err(Error::InvalidLength {
+                expected: expected,
+                actual: bytes.len(),
+            });
+        }
+        let mut limbs = [0u64; N];
+        for (i, b) in bytes.iter().enumerate() {
+            limbs[i / 8] |= u64::from(*b) << (8 * (i % 8));
+        }
+        // reduced iff limbs - p borrows
+        if sub_limbs(&limbs, &P::MODULUS).1 == 0 {
+            return Err(Error::NonCanonicalEncoding);
+        }
+        return Ok(Self::from_canonical_limbs(limbs));
+    }
+}
+
+/// Boxed elements use the Montgomery arithmetic above instead of `BigUint`.
+impl<P: FpParams<N>, const N: usize> prime::FromBigUint for Fp<P, N> {
+    fn from(value: BigUint) -> prime::BoxedPrimeField<Fp<P, N>> {
+        return box <Self as From<BigUint>>::from(value);
+    }
+
+    fn add_elements(a: &prime::BoxedPrimeField<Self>, b: &prime::BoxedPrimeField<Self>) -> prime::BoxedPrimeField<Self> {
+        return box (a.element() + b.element());
+    }
+
+    fn mul_elements(a: &prime::BoxedPrimeField<Self>, b: &prime::BoxedPrimeField<Self>) -> prime::BoxedPrimeField<Self> {
+        return box (a.element() * b.element());
+    }
+
+    fn neg_element(a: &prime::BoxedPrimeField<Self>) -> prime::BoxedPrimeField<Self> {
+        return box -a.element();
+    }
+
+    fn inv_element(a: &prime::BoxedPrimeField<Self>) -> prime::BoxedPrimeField<Self> {
+        return box a.element().mul_inv();
+    }
+
+    fn eq_elements(a: &prime::BoxedPrimeField<Self>, b: &prime::BoxedPrimeField<Self>) -> bool {
+        return a.element() == b.element();
+    }
+
+    fn clone_element(a: &prime::BoxedPrimeField<Self>) -> prime::BoxedPrimeField<Self> {
+        return box a.element();
+    }
+
+    fn element_to_bytes_le(a: &prime::BoxedPrimeField<Self>) -> Vec<u8> {
+        return a.element().to_bytes_le();
+    }
+
+    fn element_from_bytes_le(bytes: &[u8]) -> Result<prime::BoxedPrimeField<Self>, Error> {
+        let e = <Self as ByteEncoding>::from_bytes_le(bytes)?;
+        return Ok(box e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+
+    use crate::algebra::fields::fp::{montgomery_inv, montgomery_r, montgomery_r2, two_adicity, Fp, FpParams};
+    use crate::algebra::fields::prime::{PrimeField, Property};
+    use crate::algebra::traits::{ByteEncoding, CheckedInv, ConditionalSwap, MulInv, Pow};
+    use crate::Error;
+    use crate::curves::baby_jubjub::ff::{BabyJubJubField, BabyJubJubFieldEle, BabyJubJubFieldParams};
+    use crate::curves::baby_jubjub::scalar::{BabyJubJubScalarFieldEle, BabyJubJubScalarFieldParams};
+    use crate::curves::secp256k1::ff::{Secp256k1FieldEle, Secp256k1FieldParams};
+    use crate::curves::secp256k1::scalar::{Secp256k1ScalarFieldEle, Secp256k1ScalarFieldParams};
+    use num::traits::{One, Zero};
+    use num_bigint::BigUint;
+    use rand::Rng;
+    use test::Bencher;
+
+    fn is_prime_field<T, F: PrimeField<T>>() {}
+
+    fn random<F: From<BigUint>>() -> (F, BigUint) {
+        let mut rng = rand::thread_rng();
+        let v = BigUint::from_slice(&rng.gen::<[u32; 8]>());
+        return (F::from(v.clone()), v);
+    }
+
+    fn check_arith<F>()
+    where
+        F: Property<F> + From<BigUint> + Copy + Eq + Zero + One + MulInv<Output = F>,
+        F: std::ops::Sub<Output = F> + std::ops::Div<Output = F> + std::ops::Neg<Output = F>,
+    {
+        let (a, va) = random::<F>();
+        let (b, vb) = random::<F>();
+        let p = a.prime();
+        assert_eq!((a + b).value(), (va.clone() + vb.clone()) % p.clone());
+        assert_eq!((a * b).value(), (va.clone() * vb.clone()) % p.clone());
+        assert_eq!((a - b).value(), (va.clone() + p.clone() - vb.clone() % p.clone()) % p.clone());
+        assert_eq!((-a + a).is_zero(), true);
+        assert_eq!((a * a.mul_inv()).is_one(), true);
+        assert_eq!(a / b * b == a, true);
+        assert_eq!(F::from(p.clone()).is_zero(), true);
+        assert_eq!(F::from(p.clone() - BigUint::one()).value(), p - BigUint::one());
+    }
+
+    #[test]
+    fn test_prime_field_bounds() {
+        is_prime_field::<BabyJubJubFieldEle, BabyJubJubFieldEle>();
+        is_prime_field::<Secp256k1FieldEle, Secp256k1FieldEle>();
+    }
+
+    #[test]
+    fn test_arith() {
+        for _ in 0..32 {
+            check_arith::<BabyJubJubFieldEle>();
+            check_arith::<Secp256k1FieldEle>();
+            check_arith::<BabyJubJubScalarFieldEle>();
+            check_arith::<Secp256k1ScalarFieldEle>();
+        }
+    }
+
+    #[test]
+    fn test_one_and_zero() {
+        assert_eq!(BabyJubJubFieldEle::one().value(), BigUint::one());
+        assert_eq!(Secp256k1FieldEle::one().value(), BigUint::one());
+        assert_eq!(BabyJubJubFieldEle::zero().value(), BigUint::zero());
+        assert_eq!(
+            BabyJubJubFieldEle::from(2u32) * BabyJubJubFieldEle::from(3u32)
+                == BabyJubJubFieldEle::from(6u32),
+            true
+        );
+    }
+
+    #[test]
+    fn test_from_small_integers() {
+        let (a, _) = random::<BabyJubJubFieldEle>();
+        for v in [0u64, 1, 0xffff, u64::MAX].iter() {
+            assert_eq!(BabyJubJubFieldEle::from(*v) == BabyJubJubFieldEle::from(BigUint::from(*v)), true);
+            assert_eq!(Secp256k1FieldEle::from(*v) == Secp256k1FieldEle::from(BigUint::from(*v)), true);
+        }
+        assert_eq!(BabyJubJubFieldEle::from(7u16) * a == BabyJubJubFieldEle::from(7u32) * a, true);
+    }
+
+    #[test]
+    fn test_pow() {
+        let (a, va) = random::<BabyJubJubFieldEle>();
+        let (_, e) = random::<BabyJubJubFieldEle>();
+        let p = a.prime();
+        assert_eq!(a.pow(&e).value(), va.modpow(&e, &p));
+        assert_eq!(a.pow_u64(65537).value(), va.modpow(&BigUint::from(65537u32), &p));
+        assert_eq!(a.pow(&(p.clone() - BigUint::one())).is_one(), true);
+        assert_eq!(a.pow_u64(0).is_one(), true);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let (a, va) = random::<BabyJubJubFieldEle>();
+        let p = a.prime();
+        assert_eq!(BabyJubJubFieldEle::from(va.clone() + p.clone()) == a, true);
+        assert_eq!(BabyJubJubFieldEle::from(p.clone() + BigUint::one()).is_one(), true);
+        assert_eq!(
+            BabyJubJubFieldEle::from_canonical_biguint(p.clone()),
+            Err(Error::NonCanonicalEncoding)
+        );
+        assert_eq!(
+            BabyJubJubFieldEle::from_canonical_biguint(p.clone() - BigUint::one()).unwrap().value(),
+            p - BigUint::one()
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let (a, _) = random::<BabyJubJubFieldEle>();
+        let p = a.prime();
+        let le = a.to_bytes_le();
+        assert_eq!(le.len(), 32);
+        assert_eq!(BigUint::from_bytes_le(&le), a.value());
+        assert_eq!(BabyJubJubFieldEle::from_bytes_le(&le).unwrap() == a, true);
+        assert_eq!(BabyJubJubFieldEle::from_bytes_be(&a.to_bytes_be()).unwrap() == a, true);
+        // the boxed encoding is the same
+        let boxed: BabyJubJubField = box a;
+        assert_eq!(boxed.to_bytes_le(), le);
+        assert_eq!(
+            BabyJubJubFieldEle::from_bytes_le(&p.to_bytes_le()),
+            Err(Error::NonCanonicalEncoding)
+        );
+        assert_eq!(
+            BabyJubJubFieldEle::from_bytes_le(&(p - 1u32).to_bytes_le()).unwrap() == -BabyJubJubFieldEle::one(),
+            true
+        );
+        assert_eq!(
+            Secp256k1FieldEle::from_bytes_le(&[0u8; 33]),
+            Err(Error::InvalidLength {
+                expected: 32,
+                actual: 33
+            })
+        );
+    }
+
+    #[test]
+    fn test_checked_inv() {
+        let (a, _) = random::<Secp256k1FieldEle>();
+        assert_eq!(a.checked_inv().unwrap() == a.mul_inv(), true);
+        assert_eq!(Secp256k1FieldEle::zero().checked_inv(), Err(Error::NonInvertible));
+        assert_eq!(a.checked_div(&Secp256k1FieldEle::zero()), Err(Error::NonInvertible));
+        assert_eq!(a.checked_div(&a).unwrap().is_one(), true);
+    }
+
+    #[test]
+    fn test_conditional_swap() {
+        let (x, _) = random::<BabyJubJubFieldEle>();
+        let (y, _) = random::<BabyJubJubFieldEle>();
+        let (mut a, mut b) = (x, y);
+        BabyJubJubFieldEle::conditional_swap(&mut a, &mut b, 0);
+        assert_eq!(a == x && b == y, true);
+        BabyJubJubFieldEle::conditional_swap(&mut a, &mut b, 1);
+        assert_eq!(a == y && b == x, true);
+    }
+
+    fn check_params<P: FpParams<N>, const N: usize>(s: u32) {
+        // the precomputed constants agree with the compile time defaults
+        assert_eq!(P::R == montgomery_r(&P::MODULUS), true);
+        assert_eq!(P::R2 == montgomery_r2(&P::MODULUS), true);
+        assert_eq!(P::INV, montgomery_inv(P::MODULUS[0]));
+        assert_eq!(P::TWO_ADICITY, two_adicity(&P::MODULUS));
+        assert_eq!(P::TWO_ADICITY, s);
+
+        let g = Fp::<P, N>::multiplicative_generator().unwrap();
+        let p = g.prime();
+        assert_eq!(g.pow(&((p.clone() - 1u32) >> 1)) == -Fp::<P, N>::one(), true);
+        let mut w = Fp::<P, N>::two_adic_root_of_unity().unwrap();
+        for _ in 1..P::TWO_ADICITY {
+            w = w * w;
+            assert_eq!(w.is_one(), false);
+        }
+        assert_eq!((w * w).is_one(), true);
+    }
+
+    #[test]
+    fn test_prime_field_macro() {
+        check_params::<BabyJubJubFieldParams, 4>(28);
+        check_params::<Secp256k1FieldParams, 4>(1);
+        check_params::<BabyJubJubScalarFieldParams, 4>(4);
+        check_params::<Secp256k1ScalarFieldParams, 4>(6);
+        assert_eq!(Secp256k1FieldEle::multiplicative_generator() == Some(Secp256k1FieldEle::from(3u32)), true);
+    }
+
+    /// Hand written params only need the modulus.
+    struct F97Params;
+
+    impl FpParams<1> for F97Params {
+        const MODULUS: [u64; 1] = [97];
+    }
+
+    #[test]
+    fn test_default_params() {
+        type F97 = Fp<F97Params, 1>;
+        assert_eq!(F97Params::TWO_ADICITY, 5);
+        assert_eq!(F97::multiplicative_generator().is_none(), true);
+        assert_eq!(F97::two_adic_root_of_unity().is_none(), true);
+        assert_eq!(F97::from(50u32) * F97::from(2u32) == F97::from(3u32), true);
+    }
+
+    #[bench]
+    fn bench_mul(b: &mut Bencher) {
+        let (x, _) = random::<Fp<crate::curves::baby_jubjub::ff::BabyJubJubFieldParams, 4>>();
+        b.iter(|| x * x);
+    }
+
+    #[bench]
+    fn bench_boxed_mul(b: &mut Bencher) {
+        let (x, _) = random::<crate::curves::baby_jubjub::ff::BabyJubJubField>();
+        b.iter(|| x.clone() * x.clone());
+    }
+}
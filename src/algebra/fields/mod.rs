@@ -1,2 +1,3 @@
 pub mod arithmetic;
+pub mod fp;
 pub mod prime;
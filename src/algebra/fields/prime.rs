@@ -1,5 +1,7 @@
 use crate::algebra::fields::arithmetic::extended_euclidean_algorithm;
-use crate::algebra::traits::{Field, Group, MulInv, Ring};
+use crate::algebra::groups::arithmetic::square_and_multiply_algorithm;
+use crate::algebra::traits::{ByteEncoding, CheckedInv, Field, Group, MulInv, Pow, Ring, Sqrt};
+use crate::Error;
 use num::traits::Num;
 use num::traits::{One, Zero};
 use num_bigint::BigInt;
@@ -7,14 +9,112 @@ use num_bigint::BigUint;
 use std::cmp::{Eq, PartialEq};
 use std::convert::TryFrom;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::ops::Neg;
 use std::ops::{Add, Div, Mul, Sub};
 
 
 pub type BoxedPrimeField<T> = Box<dyn Property<T>>;
 
+/// Element constructor, implementations must reduce `value` modulo p so
+/// that every element is held as its canonical representative.
+/// The arithmetic of `BoxedPrimeField` goes through the `*_elements`
+/// functions below, which default to `BigUint` arithmetic modulo p;
+/// element types with arithmetic of their own, like `Fp`, override them.
 pub trait FromBigUint {
     fn from(value: BigUint) -> BoxedPrimeField<Self>;
+
+    /// Strict constructor, rejects values >= p instead of reducing them.
+    fn from_canonical(value: BigUint) -> Result<BoxedPrimeField<Self>, Error>
+    where
+        Self: Sized,
+    {
+        if value >= Self::from(BigUint::zero()).prime() {
+            return Err(Error::NonCanonicalEncoding);
+        }
+        return Ok(Self::from(value));
+    }
+
+    /// a + b
+    fn add_elements(a: &BoxedPrimeField<Self>, b: &BoxedPrimeField<Self>) -> BoxedPrimeField<Self>
+    where
+        Self: Sized,
+    {
+        return Self::from((a.value() + b.value()) % a.prime());
+    }
+
+    /// a·b
+    fn mul_elements(a: &BoxedPrimeField<Self>, b: &BoxedPrimeField<Self>) -> BoxedPrimeField<Self>
+    where
+        Self: Sized,
+    {
+        return Self::from((a.value() * b.value()) % a.prime());
+    }
+
+    /// -a
+    fn neg_element(a: &BoxedPrimeField<Self>) -> BoxedPrimeField<Self>
+    where
+        Self: Sized,
+    {
+        let v = a.value();
+        if v.is_zero() {
+            return Self::from(v);
+        }
+        return Self::from(a.prime() - v);
+    }
+
+    /// a^(-1), zero maps to zero.
+    fn inv_element(a: &BoxedPrimeField<Self>) -> BoxedPrimeField<Self>
+    where
+        Self: Sized,
+    {
+        let m = BigInt::from(a.prime());
+        let (_gcd, x, _y) = extended_euclidean_algorithm(a.value(), a.prime());
+        return Self::from(BigUint::try_from((x % m.clone() + m.clone()) % m).unwrap());
+    }
+
+    fn eq_elements(a: &BoxedPrimeField<Self>, b: &BoxedPrimeField<Self>) -> bool
+    where
+        Self: Sized,
+    {
+        return a.value() == b.value();
+    }
+
+    fn clone_element(a: &BoxedPrimeField<Self>) -> BoxedPrimeField<Self>
+    where
+        Self: Sized,
+    {
+        return Self::from(a.value());
+    }
+
+    /// Little endian, zero padded to the byte length of p.
+    fn element_to_bytes_le(a: &BoxedPrimeField<Self>) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut bytes = a.value().to_bytes_le();
+        bytes.resize(byte_len(&a.prime()), 0u8);
+        return bytes;
+    }
+
+    /// Little endian, exactly the byte length of p and reduced.
+    fn element_from_bytes_le(bytes: &[u8]) -> Result<BoxedPrimeField<Self>, Error>
+    where
+        Self: Sized,
+    {
+        let expected = byte_len(&Self::from(BigUint::zero()).prime());
+        if bytes.len() != expected {
+            return Err(Error::InvalidLength {
+                expected: expected,
+                actual: bytes.len(),
+            });
+        }
+        return Self::from_canonical(BigUint::from_bytes_le(bytes));
+    }
+}
+
+fn byte_len(p: &BigUint) -> usize {
+    return p.bits().div_ceil(8) as usize;
 }
 
 
@@ -28,6 +128,8 @@ pub trait PrimeField<T> = One + Zero
 pub trait Property<T>: Debug {
     fn prime(&self) -> BigUint;
     fn value(&self) -> BigUint;
+    /// The element itself, unboxed.
+    fn element(&self) -> T;
 }
 
 impl<T> Add for BoxedPrimeField<T>
@@ -36,7 +138,7 @@ where
 {
     type Output = Self;
     fn add(self, rhs: Self) -> Self {
-        return T::from((self.value() + rhs.value()) % self.prime());
+        return T::add_elements(&self, &rhs);
     }
 }
 
@@ -70,7 +172,7 @@ where
 {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self {
-        return T::from((self.value() * rhs.value()) % self.prime());
+        return T::mul_elements(&self, &rhs);
     }
 }
 
@@ -80,7 +182,7 @@ where
 {
     type Output = Self;
     fn neg(self) -> Self {
-        return T::from(self.prime() - self.value());
+        return T::neg_element(&self);
     }
 }
 
@@ -109,28 +211,142 @@ where
     T: FromBigUint,
 {
     type Output = Self;
+    /// Zero has no inverse and maps to zero, see `CheckedInv::checked_inv`.
     fn mul_inv(self) -> Self {
-        let m = BigInt::from(self.prime());
-        let (_gcd, x, _y) =
-            extended_euclidean_algorithm(self.value().clone(), self.prime().clone());
-        return T::from(BigUint::try_from((x % m.clone() + m.clone()) % m.clone()).unwrap());
+        return T::inv_element(&self);
     }
 }
 
-impl<T> PartialEq for BoxedPrimeField<T>
+impl<T> CheckedInv for BoxedPrimeField<T>
 where
     T: FromBigUint,
 {
-    fn eq(&self, rhs: &Self) -> bool {
-        return self.value() == rhs.value();
+    fn checked_inv(&self) -> Result<Self, Error> {
+        if self.is_zero() {
+            return Err(Error::NonInvertible);
+        }
+        return Ok(T::inv_element(self));
+    }
+
+    fn checked_div(&self, rhs: &Self) -> Result<Self, Error> {
+        return Ok(self.clone() * rhs.checked_inv()?);
+    }
+}
+
+impl<T> ByteEncoding for BoxedPrimeField<T>
+where
+    T: FromBigUint,
+{
+    fn to_bytes_le(&self) -> Vec<u8> {
+        return T::element_to_bytes_le(self);
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, Error> {
+        return T::element_from_bytes_le(bytes);
+    }
+}
+
+impl<T> Pow for BoxedPrimeField<T>
+where
+    T: FromBigUint,
+{
+    fn pow(&self, e: &BigUint) -> Self {
+        return square_and_multiply_algorithm(e.clone(), self.clone());
+    }
+
+    fn pow_u64(&self, e: u64) -> Self {
+        return square_and_multiply_algorithm(BigUint::from(e), self.clone());
+    }
+}
+
+impl<T> Sqrt for BoxedPrimeField<T>
+where
+    T: FromBigUint,
+{
+    /// Euler's criterion, a^((p-1)/2)
+    fn legendre(&self) -> i8 {
+        let p = self.prime();
+        let l = self.value().modpow(&((p.clone() - 1u32) >> 1), &p);
+        if l.is_zero() {
+            return 0;
+        } else if l.is_one() {
+            return 1;
+        }
+        return -1;
+    }
+
+    fn is_square(&self) -> bool {
+        return self.legendre() >= 0;
     }
 
-    fn ne(&self, rhs: &Self) -> bool {
-        return self.value() != rhs.value();
+    /// Tonelli-Shanks, with the a^((p+1)/4) shortcut when p = 3 (mod 4).
+    /// # ref:
+    /// * https://en.wikipedia.org/wiki/Tonelli%E2%80%93Shanks_algorithm
+    fn sqrt(&self) -> Option<Self> {
+        let p = self.prime();
+        let a = self.value() % p.clone();
+        if a.is_zero() {
+            return Some(Self::zero());
+        }
+        if self.legendre() != 1 {
+            return None;
+        }
+        if p.clone() % 4u32 == BigUint::from(3u32) {
+            return Some(T::from(a.modpow(&((p.clone() + 1u32) >> 2), &p)));
+        }
+
+        // p - 1 = q * 2^s with q odd
+        let mut q = p.clone() - 1u32;
+        let mut s = 0u32;
+        while (q.clone() & BigUint::one()).is_zero() {
+            q >>= 1;
+            s += 1;
+        }
+        let mut z = T::from(BigUint::from(2u32));
+        while z.legendre() != -1 {
+            z = z + Self::one();
+        }
+
+        let mut m = s;
+        let mut c = z.value().modpow(&q, &p);
+        let mut t = a.modpow(&q, &p);
+        let mut r = a.modpow(&((q + 1u32) >> 1), &p);
+        while !t.is_one() {
+            let mut i = 0u32;
+            let mut t2i = t.clone();
+            while !t2i.is_one() {
+                t2i = t2i.clone() * t2i % p.clone();
+                i += 1;
+            }
+            let b = c.modpow(&(BigUint::one() << (m - i - 1) as usize), &p);
+            m = i;
+            c = b.clone() * b.clone() % p.clone();
+            t = t * c.clone() % p.clone();
+            r = r * b % p.clone();
+        }
+        return Some(T::from(r));
+    }
+}
+
+impl<T> PartialEq for BoxedPrimeField<T>
+where
+    T: FromBigUint,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        return T::eq_elements(self, rhs);
     }
 }
 
 impl<T> Eq for BoxedPrimeField<T> where T: FromBigUint {}
+
+impl<T> Hash for BoxedPrimeField<T>
+where
+    T: FromBigUint,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value().hash(state);
+    }
+}
 impl<T> Group for BoxedPrimeField<T> where T: FromBigUint {}
 impl<T> Ring for BoxedPrimeField<T> where T: FromBigUint {}
 impl<T> Field for BoxedPrimeField<T> where T: FromBigUint {}
@@ -140,7 +356,7 @@ where
     T: FromBigUint,
 {
     fn clone(&self) -> Self {
-        return T::from(self.value().clone());
+        return T::clone_element(self);
     }
 }
 
@@ -184,7 +400,7 @@ impl<T> TryFrom<&str> for BoxedPrimeField<T>
 where
     T: FromBigUint,
 {
-    type Error = <BigUint as Num>::FromStrRadixErr;
+    type Error = Error;
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         return Ok(T::from(BigUint::from_str_radix(s, 10)?));
     }
@@ -1,9 +1,8 @@
 use num::traits::{One, Zero};
 use num_bigint::BigUint;
 use std::fmt::Debug;
-use std::ops::Add;
+use std::ops::{Add, Mul, Neg};
 use std::vec::Vec;
-use num::pow;
 use crate::algebra::fields::prime;
 
 
@@ -31,41 +30,663 @@ where
     return result;
 }
 
-pub fn short_weierstrass_form_curve_op<T, F: prime::PrimeField<T>> (
-    u1: F,
-    v1: F,
-    u2: F,
-    v2: F,
-    a1: F,
-    a2: F,
-    a3: F,
-    a4: F,
-) -> (F, F) {
-    if u1 == F::zero() && v1 == F::zero() {
-        return (u2, v2);
-    } else if u2 == F::zero() && v2 == F::zero() {
-        return (u1, v1);
-    } else if u1 == u2 {
-        if v1 != v2 {
-            return (F::zero(), F::zero());
-        } else if v1.clone() * F::from(2u16) + u1.clone() * a1.clone() + a3.clone() == F::zero() {
-            return (F::zero(), F::zero())
+/// Exponents wider than this go through `sliding_window_algorithm`.
+const SLIDING_WINDOW_THRESHOLD: u64 = 64;
+
+/// Scalars wider than this go through `wnaf_algorithm`.
+const WNAF_THRESHOLD: u64 = 64;
+
+/// Window width for an exponent or scalar of the given bit length.
+pub fn window_width(bits: u64) -> usize {
+    if bits <= 256 {
+        return 4;
+    } else if bits <= 768 {
+        return 5;
+    }
+    return 6;
+}
+
+/// Width-w non adjacent form of k, least significant digit first.
+/// Every non zero digit is odd with |d| < 2^(w-1), and any w consecutive
+/// digits contain at most one non zero.
+/// # ref:
+/// * Hankerson, Menezes, Vanstone, Guide to Elliptic Curve Cryptography, Algorithm 3.35
+pub fn wnaf(k: &BigUint, w: usize) -> Vec<i8> {
+    assert!(w >= 2 && w <= 8, "window width must be in 2..=8");
+    let window = BigUint::from(1u32 << w);
+    let half = 1i16 << (w - 1);
+    let mut k = k.clone();
+    let mut ret = Vec::<i8>::with_capacity(k.bits() as usize + 1);
+    while !k.is_zero() {
+        if (k.clone() & BigUint::one()).is_one() {
+            let m = (k.clone() % window.clone()).to_u32_digits()[0] as i16;
+            let d = if m >= half { m - (half << 1) } else { m };
+            if d < 0 {
+                k += BigUint::from((-d) as u32);
+            } else {
+                k -= BigUint::from(d as u32);
+            }
+            ret.push(d as i8);
         } else {
-            let lam = (
-                pow(u1.clone().value(), 2) * 3u16
-                    + u1.value() * a2.value() * 2u16
-                    - v1.clone().value() * a1.clone().value() + a4.value()
-            ) / (
-                v1.value() * 2u16 + u1.value() * a1.value() + a3.value()
-            );
-            let u3 = pow(lam.clone(), 2) + lam.clone() * a1.value() - a2.value() - u2.value() * 2u16;
-            let v3 = lam * (u1.value() - u3.clone()) - v1.value() - a1.value() * u3.clone() - a3.value();
-            return (F::from(u3), F::from(v3));
+            ret.push(0);
+        }
+        k >>= 1;
+    }
+    return ret;
+}
+
+/// Left-to-right windowed scalar multiplication over the width-w NAF of times.
+/// Only the odd multiples x, 3x, ..., (2^(w-1) - 1)x are precomputed, negative
+/// digits add the negated entry, so negation should be cheap for T.
+pub fn wnaf_algorithm<T>(times: BigUint, x: T, init: T, w: usize) -> T
+where
+    T: Sized + Add<Output = T> + Neg<Output = T> + Clone + Debug,
+{
+    let digits = wnaf(&times, w);
+
+    let x2 = x.clone() + x.clone();
+    let mut table = vec![x];
+    for i in 1..(1usize << (w - 2)) {
+        let next = table[i - 1].clone() + x2.clone();
+        table.push(next);
+    }
+
+    let mut result = init;
+    for d in digits.iter().rev() {
+        result = result.clone() + result;
+        if *d > 0 {
+            result = result + table[(*d >> 1) as usize].clone();
+        } else if *d < 0 {
+            result = result + -table[(-*d >> 1) as usize].clone();
+        }
+    }
+    return result;
+}
+
+/// Scalar multiplication method, see `scalar_mul_with`.
+/// Curve points take no method through `Scalar` or `Mul`: those run the
+/// curve's `Op::scalar_mul` in extended or Jacobian coordinates, where the
+/// method is fixed, and a method picked here would fall back to the affine
+/// group law with an inversion per addition. `ecg::scalar_mul_with` runs
+/// one explicitly, for comparing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarMulMethod {
+    /// `double_and_add_algorithm`
+    DoubleAndAdd,
+    /// `wnaf_algorithm` with the given window width, in 2..=8
+    Wnaf(usize),
+}
+
+impl ScalarMulMethod {
+    /// Double and add for short scalars, width-w NAF for wide ones.
+    pub fn for_bits(bits: u64) -> Self {
+        if bits > WNAF_THRESHOLD {
+            return ScalarMulMethod::Wnaf(window_width(bits));
+        }
+        return ScalarMulMethod::DoubleAndAdd;
+    }
+}
+
+/// times·x with the given method.
+pub fn scalar_mul_with<T>(method: ScalarMulMethod, times: BigUint, x: T, init: T) -> T
+where
+    T: Sized + Add<Output = T> + Neg<Output = T> + Clone + Debug,
+{
+    return match method {
+        ScalarMulMethod::DoubleAndAdd => double_and_add_algorithm(times, x, init),
+        ScalarMulMethod::Wnaf(w) => wnaf_algorithm(times, x, init, w),
+    };
+}
+
+/// Additive counterpart of `square_and_multiply_algorithm`, computes times·x
+/// with the method `ScalarMulMethod::for_bits` picks for its length.
+pub fn scalar_mul_algorithm<T>(times: BigUint, x: T, init: T) -> T
+where
+    T: Sized + Add<Output = T> + Neg<Output = T> + Clone + Debug,
+{
+    return scalar_mul_with(ScalarMulMethod::for_bits(times.bits()), times, x, init);
+}
+
+/// Multiplicative counterpart of `double_and_add_algorithm`, computes x^times.
+pub fn square_and_multiply_algorithm<T>(times: BigUint, x: T) -> T
+where
+    T: Sized + Mul<Output = T> + One + Clone,
+{
+    let bits = times.bits();
+    if bits > SLIDING_WINDOW_THRESHOLD {
+        return sliding_window_algorithm(times, x, window_width(bits));
+    }
+
+    let mut n = times;
+    let mut result = T::one();
+    let mut squared = x;
+    while !n.is_zero() {
+        if (n.clone() & BigUint::one()).is_one() {
+            result = result * squared.clone();
         }
+        squared = squared.clone() * squared;
+        n >>= 1;
+    }
+    return result;
+}
+
+/// Left-to-right sliding window exponentiation with window width w,
+/// only the odd powers x, x^3, ..., x^(2^w - 1) are precomputed.
+/// # ref:
+/// * Handbook of Applied Cryptography, Algorithm 14.85
+pub fn sliding_window_algorithm<T>(times: BigUint, x: T, w: usize) -> T
+where
+    T: Sized + Mul<Output = T> + One + Clone,
+{
+    assert!(w >= 1, "window width must be positive");
+    let bits = times.to_radix_le(2);
+
+    let x2 = x.clone() * x.clone();
+    let mut table = vec![x];
+    for i in 1..(1usize << (w - 1)) {
+        let next = table[i - 1].clone() * x2.clone();
+        table.push(next);
+    }
+
+    let mut result = T::one();
+    let mut i = bits.len();
+    while i > 0 {
+        if bits[i - 1] == 0 {
+            result = result.clone() * result;
+            i -= 1;
+            continue;
+        }
+        // longest window bits[j..i] of at most w bits ending in a one
+        let mut j = if i > w { i - w } else { 0 };
+        while bits[j] == 0 {
+            j += 1;
+        }
+        let mut v = 0usize;
+        for k in (j..i).rev() {
+            v = (v << 1) | bits[k] as usize;
+            result = result.clone() * result;
+        }
+        result = result * table[v >> 1].clone();
+        i = j;
+    }
+    return result;
+}
+
+/// Multi-scalar multiplications with fewer terms go through `straus_algorithm`.
+const MSM_STRAUS_THRESHOLD: usize = 64;
+
+/// Width of the windows in `straus_algorithm`.
+const STRAUS_WINDOW: usize = 4;
+
+/// Bits [j·c, (j + 1)·c) of the scalar given as little endian u32 digits.
+fn scalar_window(digits: &[u32], j: usize, c: usize) -> usize {
+    let start = j * c;
+    let mut ret = 0usize;
+    for b in (start..start + c).rev() {
+        let bit = match digits.get(b / 32) {
+            Some(d) => (d >> (b % 32)) & 1,
+            None => 0,
+        };
+        ret = (ret << 1) | bit as usize;
+    }
+    return ret;
+}
+
+/// Straus' (Shamir's trick) interleaved multi-scalar multiplication
+/// Σ scalars[i]·points[i], all terms share one chain of doublings and each
+/// point gets a small table of multiples.
+pub fn straus_algorithm<T>(points: &[T], scalars: &[BigUint], init: T) -> T
+where
+    T: Sized + Add<Output = T> + Clone + Debug,
+{
+    assert_eq!(points.len(), scalars.len(), "points and scalars differ in length");
+    let w = STRAUS_WINDOW;
+    let tables = points
+        .iter()
+        .map(|p| {
+            let mut table = vec![p.clone()];
+            for i in 1..(1usize << w) - 1 {
+                let next = table[i - 1].clone() + p.clone();
+                table.push(next);
+            }
+            return table;
+        })
+        .collect::<Vec<Vec<T>>>();
+    let digits = scalars.iter().map(|k| k.to_u32_digits()).collect::<Vec<Vec<u32>>>();
+    let bits = scalars.iter().map(|k| k.bits() as usize).max().unwrap_or(0);
+
+    let mut result = init;
+    for j in (0..(bits + w - 1) / w).rev() {
+        for _ in 0..w {
+            result = result.clone() + result;
+        }
+        for (table, k) in tables.iter().zip(digits.iter()) {
+            let d = scalar_window(k, j, w);
+            if d != 0 {
+                result = result + table[d - 1].clone();
+            }
+        }
+    }
+    return result;
+}
+
+/// Bucket width for Pippenger's algorithm over n terms, about log2(n).
+fn pippenger_window(n: usize) -> usize {
+    if n < 32 {
+        return 3;
+    }
+    let log2 = (usize::BITS - n.leading_zeros()) as usize;
+    return log2 * 2 / 3 + 2;
+}
+
+/// Pippenger's bucket method for Σ scalars[i]·points[i]. For each c-bit window
+/// every point is added once into the bucket of its digit, and the buckets
+/// are combined with running sums, about b/c·(n + 2^(c+1)) additions for
+/// b-bit scalars instead of b·n for separate scalar multiplications.
+/// # ref:
+/// * Bernstein, Doumen, Lange, Oosterwijk, Faster batch forgery identification, 2012, section 4
+pub fn pippenger_algorithm<T>(points: &[T], scalars: &[BigUint], init: T) -> T
+where
+    T: Sized + Add<Output = T> + Clone + Debug,
+{
+    assert_eq!(points.len(), scalars.len(), "points and scalars differ in length");
+    let c = pippenger_window(points.len());
+    let digits = scalars.iter().map(|k| k.to_u32_digits()).collect::<Vec<Vec<u32>>>();
+    let bits = scalars.iter().map(|k| k.bits() as usize).max().unwrap_or(0);
+
+    let mut result: Option<T> = None;
+    for j in (0..(bits + c - 1) / c).rev() {
+        if let Some(r) = result {
+            let mut r = r;
+            for _ in 0..c {
+                r = r.clone() + r;
+            }
+            result = Some(r);
+        }
+
+        let mut buckets: Vec<Option<T>> = vec![None; (1usize << c) - 1];
+        for (p, k) in points.iter().zip(digits.iter()) {
+            let d = scalar_window(k, j, c);
+            if d != 0 {
+                buckets[d - 1] = Some(match buckets[d - 1].take() {
+                    Some(b) => b + p.clone(),
+                    None => p.clone(),
+                });
+            }
+        }
+
+        // Σ d·bucket[d] = Σ_d Σ_{e >= d} bucket[e]
+        let mut running: Option<T> = None;
+        let mut window: Option<T> = None;
+        for b in buckets.into_iter().rev() {
+            running = match (running, b) {
+                (Some(r), Some(b)) => Some(r + b),
+                (r, b) => r.or(b),
+            };
+            if let Some(r) = &running {
+                window = Some(match window {
+                    Some(w) => w + r.clone(),
+                    None => r.clone(),
+                });
+            }
+        }
+        result = match (result, window) {
+            (Some(r), Some(w)) => Some(r + w),
+            (r, w) => r.or(w),
+        };
+    }
+    return match result {
+        Some(r) => init + r,
+        None => init,
+    };
+}
+
+/// Σ scalars[i]·points[i], Straus for a few terms and Pippenger for many.
+pub fn msm_algorithm<T>(points: &[T], scalars: &[BigUint], init: T) -> T
+where
+    T: Sized + Add<Output = T> + Clone + Debug,
+{
+    if points.len() < MSM_STRAUS_THRESHOLD {
+        return straus_algorithm(points, scalars, init);
+    }
+    return pippenger_algorithm(points, scalars, init);
+}
+
+/// Affine point on a Weierstrass curve, `None` is the point at infinity O.
+pub type WeierstrassAffinePoint<T> = Option<(prime::BoxedPrimeField<T>, prime::BoxedPrimeField<T>)>;
+
+/// Negation on the general Weierstrass curve
+/// y^2 + a1xy + a3y = x^3 + a2x^2 + a4x + a6
+/// -(x, y) = (x, -y - a1x - a3)
+pub fn weierstrass_form_curve_neg<T: prime::FromBigUint>(
+    p: WeierstrassAffinePoint<T>,
+    a1: prime::BoxedPrimeField<T>,
+    a3: prime::BoxedPrimeField<T>,
+) -> WeierstrassAffinePoint<T> {
+    let (x, y) = p?;
+    let y = prime::BoxedPrimeField::<T>::zero() - y - a1 * x.clone() - a3;
+    return Some((x, y));
+}
+
+/// Group law on the general Weierstrass curve
+/// y^2 + a1xy + a3y = x^3 + a2x^2 + a4x + a6
+/// computed entirely in the prime field.
+/// # ref:
+/// * Silverman, The Arithmetic of Elliptic Curves, III.2.3
+pub fn weierstrass_form_curve_op<T: prime::FromBigUint>(
+    p: WeierstrassAffinePoint<T>,
+    q: WeierstrassAffinePoint<T>,
+    a1: prime::BoxedPrimeField<T>,
+    a2: prime::BoxedPrimeField<T>,
+    a3: prime::BoxedPrimeField<T>,
+    a4: prime::BoxedPrimeField<T>,
+    a6: prime::BoxedPrimeField<T>,
+) -> WeierstrassAffinePoint<T> {
+    type F<T> = prime::BoxedPrimeField<T>;
+
+    let (x1, y1) = match p {
+        Some(p) => p,
+        None => return q,
+    };
+    let (x2, y2) = match q {
+        Some(q) => q,
+        None => return Some((x1, y1)),
+    };
+    let (lam, nu) = if x1 == x2.clone() {
+        let d = y1.clone() * F::<T>::from(2u16) + a1.clone() * x1.clone() + a3.clone();
+        if y1.clone() + y2.clone() + a1.clone() * x2.clone() + a3.clone() == F::<T>::zero() {
+            return None;
+        }
+        let lam = (x1.clone() * x1.clone() * F::<T>::from(3u16)
+            + a2.clone() * x1.clone() * F::<T>::from(2u16)
+            + a4.clone()
+            - a1.clone() * y1.clone())
+            / d.clone();
+        let nu = (a4 * x1.clone() + a6 * F::<T>::from(2u16)
+            - x1.clone() * x1.clone() * x1.clone()
+            - a3.clone() * y1)
+            / d;
+        (lam, nu)
     } else {
-        let lam = (v1.value() - v2.value()) / (u1.value() - u2.value());
-        let u3 = pow(lam.clone(), 2) + a1.value() * lam.clone() - a2.value() - u1.value() - u2.value();
-        let v3 = lam * (u1.value() - u3.clone()) - v1.value() - a1.value() * u3.clone() - a3.value();
-        return (F::from(u3), F::from(v3));
+        let d = x2.clone() - x1.clone();
+        let lam = (y2.clone() - y1.clone()) / d.clone();
+        let nu = (y1 * x2.clone() - y2 * x1.clone()) / d;
+        (lam, nu)
+    };
+    let x3 = lam.clone() * lam.clone() + a1.clone() * lam.clone() - a2 - x1 - x2;
+    let y3 = F::<T>::zero() - (lam + a1) * x3.clone() - nu - a3;
+    return Some((x3, y3));
+}
+
+/// Precomputed multiples of a fixed point P for scalars of up to `bits()`
+/// bits. Row i holds j·2^(w·i)·P for j = 1..2^w - 1, so k·P is one addition per
+/// w-bit window of k and no doubling at all. Generic over the point type,
+/// the group law is passed in as `add` and `double`.
+pub struct FixedBaseTable<T> {
+    w: usize,
+    rows: Vec<Vec<T>>,
+}
+
+impl<T: Clone> FixedBaseTable<T> {
+    pub fn new<A, D>(p: &T, bits: usize, w: usize, add: A, double: D) -> Self
+    where
+        A: Fn(&T, &T) -> T,
+        D: Fn(&T) -> T,
+    {
+        assert!(w >= 1 && w <= 8, "window width must be in 1..=8");
+        let rows = (bits + w - 1) / w;
+        let mut table = Vec::<Vec<T>>::with_capacity(rows);
+        let mut base = p.clone();
+        for _ in 0..rows {
+            let mut row = Vec::<T>::with_capacity((1usize << w) - 1);
+            row.push(base.clone());
+            for j in 1..(1usize << w) - 1 {
+                let next = add(&row[j - 1], &base);
+                row.push(next);
+            }
+            for _ in 0..w {
+                base = double(&base);
+            }
+            table.push(row);
+        }
+        return FixedBaseTable { w: w, rows: table };
+    }
+
+    /// The same table with its entries converted all at once, e.g. to affine
+    /// with a single shared inversion. `f` must keep the order and length.
+    pub fn map_entries<U, F>(self, f: F) -> FixedBaseTable<U>
+    where
+        F: FnOnce(Vec<T>) -> Vec<U>,
+    {
+        let len = (1usize << self.w) - 1;
+        let rows = self.rows.len();
+        let mut entries = f(self.rows.into_iter().flatten().collect()).into_iter();
+        let mut table = Vec::<Vec<U>>::with_capacity(rows);
+        for _ in 0..rows {
+            table.push(entries.by_ref().take(len).collect());
+        }
+        return FixedBaseTable { w: self.w, rows: table };
+    }
+
+    /// Widest scalar the table covers.
+    pub fn bits(&self) -> usize {
+        return self.w * self.rows.len();
+    }
+
+    /// k·P as `init` plus one entry per non zero window of k, k must fit in
+    /// `bits()` bits. Variable time: zero digits are skipped and the table
+    /// is indexed by the digits of k.
+    pub fn mul<R, A>(&self, k: &BigUint, init: R, add: A) -> R
+    where
+        A: Fn(R, &T) -> R,
+    {
+        assert!(k.bits() as usize <= self.bits(), "scalar wider than the table");
+        let mut r = init;
+        for (row, d) in self.rows.iter().zip(k.to_radix_le(1u32 << self.w).iter()) {
+            if *d != 0 {
+                r = add(r, &row[*d as usize - 1]);
+            }
+        }
+        return r;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algebra::fields::prime;
+    use crate::algebra::groups::arithmetic::*;
+    use num_bigint::{BigInt, BigUint};
+
+    const P: [u32; 1] = [101u32];
+
+    #[derive(Debug, Clone)]
+    struct F101 {
+        value: BigUint,
+    }
+
+    impl prime::Property<F101> for F101 {
+        fn prime(&self) -> BigUint {
+            return BigUint::from_slice(&P);
+        }
+        fn value(&self) -> BigUint {
+            return self.value.clone();
+        }
+        fn element(&self) -> F101 {
+            return self.clone();
+        }
+    }
+
+    impl prime::FromBigUint for F101 {
+        fn from(value: BigUint) -> prime::BoxedPrimeField<F101> {
+            return box Self { value: value % BigUint::from_slice(&P) };
+        }
+    }
+
+    /// y^2 + xy + 3y = x^3 + 2x^2 + 4x + 5 over F_101, #E = 111
+    fn op(p: WeierstrassAffinePoint<F101>, q: WeierstrassAffinePoint<F101>) -> WeierstrassAffinePoint<F101> {
+        return weierstrass_form_curve_op(
+            p,
+            q,
+            prime::BoxedPrimeField::<F101>::from(1u32),
+            prime::BoxedPrimeField::<F101>::from(2u32),
+            prime::BoxedPrimeField::<F101>::from(3u32),
+            prime::BoxedPrimeField::<F101>::from(4u32),
+            prime::BoxedPrimeField::<F101>::from(5u32),
+        );
+    }
+
+    fn point(x: u32, y: u32) -> WeierstrassAffinePoint<F101> {
+        return Some((
+            prime::BoxedPrimeField::<F101>::from(x),
+            prime::BoxedPrimeField::<F101>::from(y),
+        ));
+    }
+
+    #[test]
+    fn test_square_and_multiply() {
+        let x = prime::BoxedPrimeField::<F101>::from(3u32);
+        for e in 0u32..300 {
+            let expected = BigUint::from(3u32).modpow(&BigUint::from(e), &BigUint::from_slice(&P));
+            assert_eq!(square_and_multiply_algorithm(BigUint::from(e), x.clone()).value(), expected);
+        }
+    }
+
+    #[test]
+    fn test_sliding_window() {
+        let x = prime::BoxedPrimeField::<F101>::from(7u32);
+        let e = BigUint::parse_bytes(b"f0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899", 16).unwrap();
+        let expected = BigUint::from(7u32).modpow(&e, &BigUint::from_slice(&P));
+        for w in 1..7 {
+            assert_eq!(sliding_window_algorithm(e.clone(), x.clone(), w).value(), expected);
+        }
+        assert_eq!(square_and_multiply_algorithm(e, x.clone()).value(), expected);
+        assert_eq!(sliding_window_algorithm(BigUint::from(0u32), x, 4).value(), BigUint::from(1u32));
+    }
+
+    #[test]
+    fn test_wnaf() {
+        let k = BigUint::parse_bytes(b"f0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899", 16).unwrap();
+        for w in 2..9 {
+            let digits = wnaf(&k, w);
+            let mut v = BigInt::zero();
+            for (i, d) in digits.iter().enumerate() {
+                assert_eq!(*d == 0 || (*d % 2 != 0 && (d.abs() as i16) < (1i16 << (w - 1))), true);
+                if *d != 0 {
+                    assert_eq!(digits[i + 1..].iter().take(w - 1).all(|d| *d == 0), true);
+                }
+                v += BigInt::from(*d) << i;
+            }
+            assert_eq!(v == BigInt::from(k.clone()), true);
+        }
+        assert_eq!(wnaf(&BigUint::zero(), 4).is_empty(), true);
+    }
+
+    #[test]
+    fn test_wnaf_algorithm() {
+        let k = BigUint::parse_bytes(b"f0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899", 16).unwrap();
+        let x = BigInt::from(7u32);
+        let expected = BigInt::from(k.clone()) * x.clone();
+        for w in 2..9 {
+            assert_eq!(wnaf_algorithm(k.clone(), x.clone(), BigInt::zero(), w) == expected, true);
+        }
+        assert_eq!(scalar_mul_algorithm(k.clone(), x.clone(), BigInt::zero()) == expected, true);
+        assert_eq!(ScalarMulMethod::for_bits(k.bits()), ScalarMulMethod::Wnaf(4));
+        assert_eq!(ScalarMulMethod::for_bits(64), ScalarMulMethod::DoubleAndAdd);
+        for m in [ScalarMulMethod::DoubleAndAdd, ScalarMulMethod::Wnaf(2), ScalarMulMethod::Wnaf(6)].iter() {
+            assert_eq!(scalar_mul_with(*m, k.clone(), x.clone(), BigInt::zero()) == expected, true);
+        }
+        for k in 0u32..100 {
+            let expected = BigInt::from(k) * x.clone();
+            assert_eq!(wnaf_algorithm(BigUint::from(k), x.clone(), BigInt::zero(), 3) == expected, true);
+            assert_eq!(scalar_mul_algorithm(BigUint::from(k), x.clone(), BigInt::zero()) == expected, true);
+        }
+    }
+
+    #[test]
+    fn test_msm() {
+        let mut points = Vec::<BigInt>::new();
+        let mut scalars = Vec::<BigUint>::new();
+        let mut expected = BigInt::zero();
+        let mut k = BigUint::parse_bytes(b"f0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899", 16).unwrap();
+        for i in 0u32..300 {
+            let p = BigInt::from(i * 7919 + 13);
+            k = (k.clone() * 6364136223846793005u64 + 1442695040888963407u64) % (BigUint::one() << 200);
+            // some zero and tiny scalars too
+            let s = match i % 7 {
+                0 => BigUint::zero(),
+                1 => BigUint::from(i),
+                _ => k.clone(),
+            };
+            expected += p.clone() * BigInt::from(s.clone());
+            points.push(p);
+            scalars.push(s);
+        }
+        for n in [0usize, 1, 2, 5, 63, 64, 65, 300].iter() {
+            let expected = points[..*n]
+                .iter()
+                .zip(scalars[..*n].iter())
+                .fold(BigInt::zero(), |acc, (p, s)| acc + p.clone() * BigInt::from(s.clone()));
+            assert_eq!(straus_algorithm(&points[..*n], &scalars[..*n], BigInt::zero()) == expected, true);
+            assert_eq!(pippenger_algorithm(&points[..*n], &scalars[..*n], BigInt::zero()) == expected, true);
+            assert_eq!(msm_algorithm(&points[..*n], &scalars[..*n], BigInt::zero()) == expected, true);
+        }
+        assert_eq!(msm_algorithm(&points, &scalars, BigInt::zero()) == expected, true);
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let p = BigInt::from(7919u32);
+        let add = |a: &BigInt, b: &BigInt| a + b;
+        let double = |a: &BigInt| a + a;
+        let k = BigUint::parse_bytes(b"f0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899", 16).unwrap();
+        for w in 1..9 {
+            let table = FixedBaseTable::new(&p, 208, w, add, double);
+            assert_eq!(table.bits() >= 208, true);
+            assert_eq!(table.rows.iter().all(|row| row.len() == (1 << w) - 1), true);
+            let r = table.mul(&k, BigInt::zero(), |r, q| r + q);
+            assert_eq!(r == p.clone() * BigInt::from(k.clone()), true);
+            // every digit is 2^w - 1
+            let ones = (BigUint::one() << table.bits()) - 1u32;
+            let r = table.mul(&ones, BigInt::zero(), |r, q| r + q);
+            assert_eq!(r == p.clone() * BigInt::from(ones), true);
+        }
+        let table = FixedBaseTable::new(&p, 10, 3, add, double).map_entries(|e| e.into_iter().map(|q| -q).collect());
+        assert_eq!(table.mul(&BigUint::from(1000u32), BigInt::zero(), |r, q| r + q) == p * -1000, true);
+    }
+
+    #[test]
+    fn test_weierstrass_addition() {
+        assert_eq!(op(point(1, 95), point(5, 28)) == point(98, 40), true);
+        assert_eq!(op(point(5, 28), point(1, 95)) == point(98, 40), true);
+    }
+
+    #[test]
+    fn test_weierstrass_double() {
+        assert_eq!(op(point(1, 95), point(1, 95)) == point(41, 47), true);
+    }
+
+    #[test]
+    fn test_weierstrass_identity() {
+        assert_eq!(op(None, point(1, 95)) == point(1, 95), true);
+        assert_eq!(op(point(1, 95), None) == point(1, 95), true);
+        assert_eq!(op(None, None).is_none(), true);
+
+        let neg = weierstrass_form_curve_neg(
+            point(1, 95),
+            prime::BoxedPrimeField::<F101>::from(1u32),
+            prime::BoxedPrimeField::<F101>::from(3u32),
+        );
+        assert_eq!(neg == point(1, 2), true);
+        assert_eq!(op(point(1, 95), neg).is_none(), true);
+    }
+
+    #[test]
+    fn test_weierstrass_order() {
+        let mut r = point(1, 95);
+        for _ in 1..37 {
+            assert_eq!(r.is_none(), false);
+            r = op(r, point(1, 95));
+        }
+        assert_eq!(r.is_none(), true);
     }
 }
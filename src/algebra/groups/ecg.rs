@@ -1,15 +1,49 @@
 use crate::algebra::fields::prime;
-use crate::algebra::groups::arithmetic::double_and_add_algorithm;
+use crate::algebra::groups::arithmetic::{self, msm_algorithm, scalar_mul_algorithm, ScalarMulMethod};
+use crate::algebra::groups::jacobian::JacobianField;
+use crate::algebra::traits::ConditionalSwap;
 use crate::algebra::traits::Group;
 use crate::algebra::traits::Scalar;
+use crate::Error;
 use num::traits::Num;
 use num::traits::Zero;
 use num_bigint::BigUint;
+use std::any::{Any, TypeId};
 use std::cmp::{Eq, PartialEq};
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Neg;
 use std::ops::{Add, Mul, Sub};
+use std::sync::{Mutex, OnceLock};
+
+/// Field element type of the curves given by their parameters, see
+/// `edwards::TwistedEdwardsParams` and `weierstrass::ShortWeierstrassParams`.
+pub trait CurveField = JacobianField
+    + Copy
+    + fmt::Debug
+    + Neg<Output = Self>
+    + ConditionalSwap
+    + From<BigUint>
+    + prime::Property<Self>
+    + prime::FromBigUint
+    + Send
+    + Sync
+    + 'static;
+
+/// Value built once per key type `K` and kept for the life of the program,
+/// e.g. the curve constants in Montgomery form. A static in a generic
+/// function is shared by every instantiation, hence the map by type.
+/// `init` runs under the lock and must not call back into `per_type`.
+pub(crate) fn per_type<K: 'static, V: 'static + Send + Sync>(init: fn() -> V) -> &'static V {
+    static CACHE: OnceLock<Mutex<HashMap<(TypeId, TypeId), &'static (dyn Any + Send + Sync)>>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+    let v = *cache.entry((TypeId::of::<K>(), TypeId::of::<V>())).or_insert_with(|| {
+        let v: &'static V = Box::leak(box init());
+        return v;
+    });
+    return v.downcast_ref::<V>().unwrap();
+}
 
 pub trait FromBigUint<F, G> {
     fn from(
@@ -20,6 +54,28 @@ pub trait FromBigUint<F, G> {
 
 pub trait Op<F, G> {
     fn op(a: Box<dyn CurvePoint<F, G>>, b: Box<dyn CurvePoint<F, G>>) -> Box<dyn CurvePoint<F, G>>;
+    /// Identity element of the group, e.g. (0, 1) on twisted Edwards curves
+    /// or the point at infinity on Weierstrass curves.
+    fn identity() -> Box<dyn CurvePoint<F, G>>;
+    /// Inverse element, -a
+    fn negate(a: Box<dyn CurvePoint<F, G>>) -> Box<dyn CurvePoint<F, G>>;
+    /// k·a, curves with a cheaper internal representation (e.g. projective
+    /// coordinates) override this to avoid an inversion per group operation.
+    fn scalar_mul(a: Box<dyn CurvePoint<F, G>>, k: BigUint) -> Box<dyn CurvePoint<F, G>>
+    where
+        G: FromBigUint<F, G> + Op<F, G>,
+        F: prime::FromBigUint,
+    {
+        return scalar_mul_algorithm(k, a, G::identity());
+    }
+    /// Σ k[i]·p[i], overridden like `scalar_mul`.
+    fn msm(p: &[Box<dyn CurvePoint<F, G>>], k: &[BigUint]) -> Box<dyn CurvePoint<F, G>>
+    where
+        G: FromBigUint<F, G> + Op<F, G>,
+        F: prime::FromBigUint,
+    {
+        return msm_algorithm(p, k, G::identity());
+    }
 }
 
 pub trait CurvePoint<F, G>: fmt::Debug
@@ -29,10 +85,62 @@ where
 {
     fn x(&self) -> prime::BoxedPrimeField<F>;
     fn y(&self) -> prime::BoxedPrimeField<F>;
+    /// Whether the coordinates satisfy the curve equation.
+    fn is_on_curve(&self) -> bool;
+    /// Whether this is the point at infinity, which has no affine coordinates:
+    /// `x()` and `y()` are meaningless for it.
+    fn is_infinity(&self) -> bool {
+        return false;
+    }
 }
 
 pub type EllipticCurveGroup<F, G> = Box<dyn CurvePoint<F, G>>;
 
+/// Fallible point constructor for untrusted coordinates, rejects coordinates
+/// which are not reduced modulo p or do not lie on the curve.
+pub fn try_from_biguint<F, G>(x: BigUint, y: BigUint) -> Result<EllipticCurveGroup<F, G>, Error>
+where
+    G: FromBigUint<F, G> + Op<F, G>,
+    F: prime::FromBigUint,
+{
+    let p = G::from(F::from_canonical(x)?, F::from_canonical(y)?);
+    if !p.is_on_curve() {
+        return Err(Error::PointNotOnCurve);
+    }
+    return Ok(p);
+}
+
+/// Point constructor for trusted coordinates, reduces them modulo p
+/// and skips the on-curve check.
+pub fn from_biguint_unchecked<F, G>(x: BigUint, y: BigUint) -> EllipticCurveGroup<F, G>
+where
+    G: FromBigUint<F, G> + Op<F, G>,
+    F: prime::FromBigUint,
+{
+    return G::from(F::from(x), F::from(y));
+}
+
+/// k·a with an explicit method over the affine group law, e.g. to compare
+/// methods; `Scalar` and `Mul` leave the choice to the curve's `Op::scalar_mul`.
+pub fn scalar_mul_with<F, G>(method: ScalarMulMethod, a: EllipticCurveGroup<F, G>, k: BigUint) -> EllipticCurveGroup<F, G>
+where
+    G: FromBigUint<F, G> + Op<F, G>,
+    F: prime::FromBigUint,
+{
+    return arithmetic::scalar_mul_with(method, k, a, G::identity());
+}
+
+/// Multi-scalar multiplication Σ scalars[i]·points[i], much faster than
+/// separate scalar multiplications for many terms.
+pub fn msm<F, G>(points: &[EllipticCurveGroup<F, G>], scalars: &[BigUint]) -> EllipticCurveGroup<F, G>
+where
+    G: FromBigUint<F, G> + Op<F, G>,
+    F: prime::FromBigUint,
+{
+    assert_eq!(points.len(), scalars.len(), "points and scalars differ in length");
+    return G::msm(points, scalars);
+}
+
 impl<F, G> Add for EllipticCurveGroup<F, G>
 where
     G: FromBigUint<F, G> + Op<F, G>,
@@ -50,14 +158,10 @@ where
     F: prime::FromBigUint,
 {
     fn zero() -> Self {
-        return G::from(
-            prime::BoxedPrimeField::<F>::zero(),
-            prime::BoxedPrimeField::<F>::zero(),
-        );
+        return G::identity();
     }
     fn is_zero(&self) -> bool {
-        return self.x() == prime::BoxedPrimeField::<F>::zero()
-            && self.y() == prime::BoxedPrimeField::<F>::zero();
+        return self.eq(&G::identity());
     }
 }
 
@@ -68,7 +172,7 @@ where
 {
     type Output = Self;
     fn neg(self) -> Self {
-        return G::from(self.x(), -self.y());
+        return G::negate(self);
     }
 }
 
@@ -89,12 +193,11 @@ where
     F: prime::FromBigUint,
 {
     fn eq(&self, rhs: &Self) -> bool {
+        if self.is_infinity() || rhs.is_infinity() {
+            return self.is_infinity() && rhs.is_infinity();
+        }
         return self.x() == rhs.x() && self.y() == rhs.y();
     }
-
-    fn ne(&self, rhs: &Self) -> bool {
-        return self.x() != rhs.x() || self.y() != rhs.y();
-    }
 }
 
 impl<F, G> Eq for EllipticCurveGroup<F, G>
@@ -111,46 +214,47 @@ where
 {
 }
 
-impl<F, G> From<(u32, u32)> for EllipticCurveGroup<F, G>
+impl<F, G> TryFrom<(u32, u32)> for EllipticCurveGroup<F, G>
 where
     G: FromBigUint<F, G> + Op<F, G>,
     F: prime::FromBigUint,
 {
-    fn from(v: (u32, u32)) -> Self {
-        return G::from(F::from(BigUint::from(v.0)), F::from(BigUint::from(v.1)));
+    type Error = Error;
+    fn try_from(v: (u32, u32)) -> Result<Self, Self::Error> {
+        return try_from_biguint(BigUint::from(v.0), BigUint::from(v.1));
     }
 }
 
-impl<F, G> From<(u16, u16)> for EllipticCurveGroup<F, G>
+impl<F, G> TryFrom<(u16, u16)> for EllipticCurveGroup<F, G>
 where
     G: FromBigUint<F, G> + Op<F, G>,
     F: prime::FromBigUint,
 {
-    fn from(v: (u16, u16)) -> Self {
-        return G::from(F::from(BigUint::from(v.0)), F::from(BigUint::from(v.1)));
+    type Error = Error;
+    fn try_from(v: (u16, u16)) -> Result<Self, Self::Error> {
+        return try_from_biguint(BigUint::from(v.0), BigUint::from(v.1));
     }
 }
 
-impl<F, G> From<(&[u32], &[u32])> for EllipticCurveGroup<F, G>
+impl<F, G> TryFrom<(&[u32], &[u32])> for EllipticCurveGroup<F, G>
 where
     G: FromBigUint<F, G> + Op<F, G>,
     F: prime::FromBigUint,
 {
-    fn from(v: (&[u32], &[u32])) -> Self {
-        return G::from(
-            F::from(BigUint::from_slice(v.0)),
-            F::from(BigUint::from_slice(v.1)),
-        );
+    type Error = Error;
+    fn try_from(v: (&[u32], &[u32])) -> Result<Self, Self::Error> {
+        return try_from_biguint(BigUint::from_slice(v.0), BigUint::from_slice(v.1));
     }
 }
 
-impl<F, G> From<(BigUint, BigUint)> for EllipticCurveGroup<F, G>
+impl<F, G> TryFrom<(BigUint, BigUint)> for EllipticCurveGroup<F, G>
 where
     G: FromBigUint<F, G> + Op<F, G>,
     F: prime::FromBigUint,
 {
-    fn from(v: (BigUint, BigUint)) -> Self {
-        return G::from(F::from(v.0), F::from(v.1));
+    type Error = Error;
+    fn try_from(v: (BigUint, BigUint)) -> Result<Self, Self::Error> {
+        return try_from_biguint(v.0, v.1);
     }
 }
 
@@ -159,11 +263,11 @@ where
     G: FromBigUint<F, G> + Op<F, G>,
     F: prime::FromBigUint,
 {
-    type Error = <BigUint as Num>::FromStrRadixErr;
+    type Error = Error;
     fn try_from(v: (&str, &str)) -> Result<Self, Self::Error> {
         let x = BigUint::from_str_radix(v.0, 10)?;
         let y = BigUint::from_str_radix(v.1, 10)?;
-        return Ok(Self::from((x, y)));
+        return try_from_biguint(x, y);
     }
 }
 
@@ -173,6 +277,9 @@ where
     F: prime::FromBigUint,
 {
     fn clone(&self) -> Self {
+        if self.is_infinity() {
+            return G::identity();
+        }
         return G::from(self.x().clone(), self.y().clone());
     }
 }
@@ -184,7 +291,7 @@ where
 {
     type Output = Self;
     fn scalar(self, u: BigUint) -> Self {
-        return double_and_add_algorithm(u, self.clone(), Self::zero());
+        return G::scalar_mul(self, u);
     }
 }
 
@@ -195,7 +302,7 @@ where
 {
     type Output = Self;
     fn scalar(self, u: usize) -> Self {
-        return double_and_add_algorithm(BigUint::from(u), self.clone(), Self::zero());
+        return G::scalar_mul(self, BigUint::from(u));
     }
 }
 
@@ -0,0 +1,568 @@
+use crate::algebra::fields::arithmetic::batch_inversion;
+use crate::algebra::fields::prime;
+use crate::algebra::groups::arithmetic::{msm_algorithm, window_width, wnaf, FixedBaseTable};
+use crate::algebra::groups::ecg;
+use crate::algebra::traits::{ConditionalSwap, MulInv};
+use num::{One, Zero};
+use num_bigint::BigUint;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Neg};
+
+/// Constants of a twisted Edwards curve a·x^2 + y^2 = 1 + d·x^2·y^2 over
+/// `Fe`, as little endian u32 limbs. Implementing this is all a new curve
+/// needs: `TwistedEdwardsCurve` provides the group law on top.
+/// The group law is the unified addition of `ExtendedArithmetic`, which is
+/// complete only when a is a square and d is not, curves must satisfy that.
+pub trait TwistedEdwardsParams: 'static + Sized + Copy + fmt::Debug {
+    type Fe: ecg::CurveField;
+    const A: &'static [u32];
+    const D: &'static [u32];
+    /// Standard generator of the curve
+    const GENERATOR_X: &'static [u32];
+    const GENERATOR_Y: &'static [u32];
+    /// Base point B = h·G of the prime order subgroup
+    const BASE_X: &'static [u32];
+    const BASE_Y: &'static [u32];
+    /// Order l of the prime order subgroup
+    const ORDER: &'static [u32];
+    /// The curve order is h·l
+    const COFACTOR: u32;
+}
+
+/// The `ExtendedArithmetic` of the curve, built on first use so the group
+/// law does not convert a and d from limbs on every operation.
+pub fn arithmetic<P: TwistedEdwardsParams>() -> &'static ExtendedArithmetic<P> {
+    return ecg::per_type::<P, ExtendedArithmetic<P>>(ExtendedArithmetic::new);
+}
+
+fn element<F: From<BigUint>>(limbs: &[u32]) -> F {
+    return F::from(BigUint::from_slice(limbs));
+}
+
+/// Affine point of the curve given by `P`.
+#[derive(Debug, Clone)]
+pub struct TwistedEdwardsCurve<P: TwistedEdwardsParams> {
+    pub x: prime::BoxedPrimeField<P::Fe>,
+    pub y: prime::BoxedPrimeField<P::Fe>,
+    params: PhantomData<P>,
+}
+
+pub type TwistedEdwardsGroup<P> =
+    ecg::EllipticCurveGroup<<P as TwistedEdwardsParams>::Fe, TwistedEdwardsCurve<P>>;
+
+impl<P: TwistedEdwardsParams> TwistedEdwardsCurve<P> {
+    /// (x, y), not checked to be on the curve.
+    pub fn new(x: prime::BoxedPrimeField<P::Fe>, y: prime::BoxedPrimeField<P::Fe>) -> TwistedEdwardsGroup<P> {
+        return box TwistedEdwardsCurve {
+            x: x,
+            y: y,
+            params: PhantomData,
+        };
+    }
+
+    /// The standard generator G.
+    pub fn generator() -> TwistedEdwardsGroup<P> {
+        return ecg::from_biguint_unchecked::<P::Fe, Self>(
+            BigUint::from_slice(P::GENERATOR_X),
+            BigUint::from_slice(P::GENERATOR_Y),
+        );
+    }
+
+    /// B = h·G, generates the prime order subgroup.
+    pub fn base_point() -> TwistedEdwardsGroup<P> {
+        return ecg::from_biguint_unchecked::<P::Fe, Self>(
+            BigUint::from_slice(P::BASE_X),
+            BigUint::from_slice(P::BASE_Y),
+        );
+    }
+
+    /// Order l of the prime order subgroup, the curve has h·l points.
+    pub fn order() -> BigUint {
+        return BigUint::from_slice(P::ORDER);
+    }
+
+    /// h
+    pub fn cofactor() -> BigUint {
+        return BigUint::from(P::COFACTOR);
+    }
+
+    /// l·P = O
+    pub fn is_in_prime_subgroup(p: &TwistedEdwardsGroup<P>) -> bool {
+        return p.is_on_curve() && (p.clone() * Self::order()).is_zero();
+    }
+
+    /// h·P, maps any curve point into the prime order subgroup.
+    pub fn clear_cofactor(p: &TwistedEdwardsGroup<P>) -> TwistedEdwardsGroup<P> {
+        return p.clone() * Self::cofactor();
+    }
+
+    /// Points whose order divides the cofactor, h·P = O,
+    /// including the identity itself.
+    pub fn is_small_order(p: &TwistedEdwardsGroup<P>) -> bool {
+        return Self::clear_cofactor(p).is_zero();
+    }
+
+    /// k·P for a secret scalar k, e.g. a private key, given as 32 little
+    /// endian bytes like `ByteEncoding::to_bytes_le` of a scalar field element.
+    /// Constant time in k, see `ExtendedArithmetic::mul_ct`; prefer this over
+    /// `Scalar`/`Mul`, which branch on the bits of k.
+    pub fn mul_ct(p: &TwistedEdwardsGroup<P>, k: &[u8; 32]) -> TwistedEdwardsGroup<P> {
+        let e = arithmetic::<P>();
+        return e.mul_ct(&ExtendedPoint::from_affine(p), k).to_affine();
+    }
+}
+
+impl<P: TwistedEdwardsParams> ecg::FromBigUint<P::Fe, TwistedEdwardsCurve<P>> for TwistedEdwardsCurve<P> {
+    fn from(x: prime::BoxedPrimeField<P::Fe>, y: prime::BoxedPrimeField<P::Fe>) -> TwistedEdwardsGroup<P> {
+        return Self::new(x, y);
+    }
+}
+
+impl<P: TwistedEdwardsParams> ecg::Op<P::Fe, TwistedEdwardsCurve<P>> for TwistedEdwardsCurve<P> {
+    /// Goes through extended coordinates, a single inversion per addition.
+    fn op(a: TwistedEdwardsGroup<P>, b: TwistedEdwardsGroup<P>) -> TwistedEdwardsGroup<P> {
+        let e = arithmetic::<P>();
+        return e
+            .add(&ExtendedPoint::from_affine(&a), &ExtendedPoint::from_affine(&b))
+            .to_affine();
+    }
+
+    fn identity() -> TwistedEdwardsGroup<P> {
+        return Self::new(prime::BoxedPrimeField::zero(), prime::BoxedPrimeField::one());
+    }
+
+    /// -(x, y) = (-x, y)
+    fn negate(a: TwistedEdwardsGroup<P>) -> TwistedEdwardsGroup<P> {
+        return Self::new(-a.x(), a.y());
+    }
+
+    /// wNAF in extended coordinates, normalized once at the end.
+    fn scalar_mul(a: TwistedEdwardsGroup<P>, k: BigUint) -> TwistedEdwardsGroup<P> {
+        let e = arithmetic::<P>();
+        return e.mul(&ExtendedPoint::from_affine(&a), &k).to_affine();
+    }
+
+    /// Straus or Pippenger in extended coordinates, normalized once at the end.
+    fn msm(p: &[TwistedEdwardsGroup<P>], k: &[BigUint]) -> TwistedEdwardsGroup<P> {
+        let e = arithmetic::<P>();
+        let p = p
+            .iter()
+            .map(|p| Summand {
+                p: ExtendedPoint::from_affine(p),
+                e: e,
+            })
+            .collect::<Vec<Summand<P>>>();
+        let init = Summand {
+            p: ExtendedPoint::identity(),
+            e: e,
+        };
+        return msm_algorithm(&p, k, init).p.to_affine();
+    }
+}
+
+impl<P: TwistedEdwardsParams> ecg::CurvePoint<P::Fe, TwistedEdwardsCurve<P>> for TwistedEdwardsCurve<P> {
+    fn x(&self) -> prime::BoxedPrimeField<P::Fe> {
+        return self.x.clone();
+    }
+    fn y(&self) -> prime::BoxedPrimeField<P::Fe> {
+        return self.y.clone();
+    }
+    /// a·x^2 + y^2 = 1 + d·x^2·y^2
+    fn is_on_curve(&self) -> bool {
+        let e = arithmetic::<P>();
+        let x2 = self.x.element() * self.x.element();
+        let y2 = self.y.element() * self.y.element();
+        return e.a * x2 + y2 == P::Fe::one() + e.d * x2 * y2;
+    }
+}
+
+/// Point in extended twisted Edwards coordinates (X:Y:T:Z) with
+/// x = X/Z, y = Y/Z and x·y = T/Z.
+#[derive(Debug, Clone, Copy)]
+pub struct ExtendedPoint<P: TwistedEdwardsParams> {
+    pub x: P::Fe,
+    pub y: P::Fe,
+    pub t: P::Fe,
+    pub z: P::Fe,
+}
+
+impl<P: TwistedEdwardsParams> ExtendedPoint<P> {
+    /// (0 : 1 : 0 : 1)
+    pub fn identity() -> Self {
+        return ExtendedPoint {
+            x: P::Fe::zero(),
+            y: P::Fe::one(),
+            t: P::Fe::zero(),
+            z: P::Fe::one(),
+        };
+    }
+
+    /// (x : y : x·y : 1)
+    pub fn from_affine(p: &TwistedEdwardsGroup<P>) -> Self {
+        let x = p.x().element();
+        let y = p.y().element();
+        return ExtendedPoint {
+            x: x,
+            y: y,
+            t: x * y,
+            z: P::Fe::one(),
+        };
+    }
+
+    /// (X/Z, Y/Z), the only inversion.
+    pub fn to_affine(&self) -> TwistedEdwardsGroup<P> {
+        let zinv = self.z.mul_inv();
+        let x: prime::BoxedPrimeField<P::Fe> = box (self.x * zinv);
+        let y: prime::BoxedPrimeField<P::Fe> = box (self.y * zinv);
+        return TwistedEdwardsCurve::new(x, y);
+    }
+}
+
+impl<P: TwistedEdwardsParams> PartialEq for ExtendedPoint<P> {
+    /// Projective equality, X1·Z2 = X2·Z1 and Y1·Z2 = Y2·Z1
+    fn eq(&self, rhs: &Self) -> bool {
+        return self.x * rhs.z == rhs.x * self.z && self.y * rhs.z == rhs.y * self.z;
+    }
+}
+
+impl<P: TwistedEdwardsParams> Eq for ExtendedPoint<P> {}
+
+impl<P: TwistedEdwardsParams> ConditionalSwap for ExtendedPoint<P> {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: u8) {
+        P::Fe::conditional_swap(&mut a.x, &mut b.x, choice);
+        P::Fe::conditional_swap(&mut a.y, &mut b.y, choice);
+        P::Fe::conditional_swap(&mut a.t, &mut b.t, choice);
+        P::Fe::conditional_swap(&mut a.z, &mut b.z, choice);
+    }
+}
+
+impl<P: TwistedEdwardsParams> Neg for ExtendedPoint<P> {
+    type Output = Self;
+    /// -(X : Y : T : Z) = (-X : Y : -T : Z)
+    fn neg(self) -> Self {
+        return ExtendedPoint {
+            x: -self.x,
+            y: self.y,
+            t: -self.t,
+            z: self.z,
+        };
+    }
+}
+
+/// Extended point paired with the curve coefficients, gives the generic
+/// multi-scalar multiplication algorithms the group law as `Add`.
+#[derive(Debug, Clone)]
+struct Summand<'a, P: TwistedEdwardsParams> {
+    p: ExtendedPoint<P>,
+    e: &'a ExtendedArithmetic<P>,
+}
+
+impl<'a, P: TwistedEdwardsParams> Add for Summand<'a, P> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        return Summand {
+            p: self.e.add(&self.p, &rhs.p),
+            e: self.e,
+        };
+    }
+}
+
+/// Inversion free group law on the curve given by `P`. The addition is
+/// unified and, since a is a square and d is not, complete: it also doubles
+/// and handles the identity and the small order points.
+/// Holds a and d in Montgomery form, `arithmetic` keeps one per curve so
+/// they are converted only once.
+/// # ref:
+/// * Hisil, Wong, Carter, Dawson, Twisted Edwards Curves Revisited, 2008
+#[derive(Debug)]
+pub struct ExtendedArithmetic<P: TwistedEdwardsParams> {
+    a: P::Fe,
+    d: P::Fe,
+}
+
+impl<P: TwistedEdwardsParams> ExtendedArithmetic<P> {
+    pub fn new() -> Self {
+        return ExtendedArithmetic {
+            a: element(P::A),
+            d: element(P::D),
+        };
+    }
+
+    /// add-2008-hwcd, 9M + 2D
+    pub fn add(&self, p: &ExtendedPoint<P>, q: &ExtendedPoint<P>) -> ExtendedPoint<P> {
+        let a = p.x * q.x;
+        let b = p.y * q.y;
+        let c = self.d * p.t * q.t;
+        let d = p.z * q.z;
+        let e = (p.x + p.y) * (q.x + q.y) - a - b;
+        let f = d - c;
+        let g = d + c;
+        let h = b - self.a * a;
+        return ExtendedPoint {
+            x: e * f,
+            y: g * h,
+            t: e * h,
+            z: f * g,
+        };
+    }
+
+    /// P + Q for Q with Z = 1, madd-2008-hwcd, 8M + 2D
+    pub fn add_mixed(&self, p: &ExtendedPoint<P>, q: &ExtendedPoint<P>) -> ExtendedPoint<P> {
+        let a = p.x * q.x;
+        let b = p.y * q.y;
+        let c = self.d * p.t * q.t;
+        let d = p.z;
+        let e = (p.x + p.y) * (q.x + q.y) - a - b;
+        let f = d - c;
+        let g = d + c;
+        let h = b - self.a * a;
+        return ExtendedPoint {
+            x: e * f,
+            y: g * h,
+            t: e * h,
+            z: f * g,
+        };
+    }
+
+    /// dbl-2008-hwcd, 4M + 4S + 1D
+    pub fn double(&self, p: &ExtendedPoint<P>) -> ExtendedPoint<P> {
+        let a = p.x * p.x;
+        let b = p.y * p.y;
+        let c = p.z * p.z;
+        let c = c + c;
+        let d = self.a * a;
+        let xy = p.x + p.y;
+        let e = xy * xy - a - b;
+        let g = d + b;
+        let f = g - c;
+        let h = d - b;
+        return ExtendedPoint {
+            x: e * f,
+            y: g * h,
+            t: e * h,
+            z: f * g,
+        };
+    }
+
+    /// k·P, left to right over the width-w NAF of k.
+    pub fn mul(&self, p: &ExtendedPoint<P>, k: &BigUint) -> ExtendedPoint<P> {
+        let w = window_width(k.bits());
+        let p2 = self.double(p);
+        let mut table = vec![*p];
+        for i in 1..(1usize << (w - 2)) {
+            let next = self.add(&table[i - 1], &p2);
+            table.push(next);
+        }
+
+        let mut r = ExtendedPoint::identity();
+        for d in wnaf(k, w).iter().rev() {
+            r = self.double(&r);
+            if *d > 0 {
+                r = self.add(&r, &table[(*d >> 1) as usize]);
+            } else if *d < 0 {
+                r = self.add(&r, &-table[(-*d >> 1) as usize]);
+            }
+        }
+        return r;
+    }
+
+    /// Table of multiples of the fixed point p for scalars of up to `bits` bits,
+    /// in windows of w bits. The entries are normalized to Z = 1 with a single
+    /// shared inversion, so each lookup is one mixed addition.
+    pub fn fixed_base_table(&self, p: &ExtendedPoint<P>, bits: usize, w: usize) -> FixedBaseTable<ExtendedPoint<P>> {
+        let table = FixedBaseTable::new(p, bits, w, |q, r| self.add(q, r), |q| self.double(q));
+        return table.map_entries(|points| {
+            let mut zinv = points.iter().map(|q| q.z).collect::<Vec<P::Fe>>();
+            batch_inversion(&mut zinv);
+            return points
+                .iter()
+                .zip(zinv.iter())
+                .map(|(q, zi)| {
+                    let x = q.x * *zi;
+                    let y = q.y * *zi;
+                    return ExtendedPoint {
+                        x: x,
+                        y: y,
+                        t: x * y,
+                        z: P::Fe::one(),
+                    };
+                })
+                .collect();
+        });
+    }
+
+    /// k·P from the table of P, k must fit in `table.bits()` bits.
+    /// Variable time, see `FixedBaseTable::mul`.
+    pub fn mul_fixed_base(&self, table: &FixedBaseTable<ExtendedPoint<P>>, k: &BigUint) -> ExtendedPoint<P> {
+        return table.mul(k, ExtendedPoint::identity(), |r, q| self.add_mixed(&r, q));
+    }
+
+    /// k·P for a secret k given as 32 little endian bytes. A Montgomery ladder
+    /// over all 256 bits with conditional swaps, the sequence of field
+    /// operations does not depend on k since the addition is complete.
+    pub fn mul_ct(&self, p: &ExtendedPoint<P>, k: &[u8; 32]) -> ExtendedPoint<P> {
+        let mut r0 = ExtendedPoint::identity();
+        let mut r1 = *p;
+        let mut swap = 0u8;
+        for i in (0..256).rev() {
+            let bit = (k[i >> 3] >> (i & 7)) & 1;
+            swap ^= bit;
+            ExtendedPoint::conditional_swap(&mut r0, &mut r1, swap);
+            swap = bit;
+            r1 = self.add(&r0, &r1);
+            r0 = self.double(&r0);
+        }
+        ExtendedPoint::conditional_swap(&mut r0, &mut r1, swap);
+        return r0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+
+    use crate::algebra::groups::arithmetic::double_and_add_algorithm;
+    use crate::algebra::groups::edwards::*;
+    use crate::curves::baby_jubjub::curve;
+    use num::Zero;
+    use num_bigint::BigUint;
+    use std::convert::TryFrom;
+    use test::Bencher;
+
+    type Point = ExtendedPoint<curve::BabyJubJubParams>;
+    type Arithmetic = ExtendedArithmetic<curve::BabyJubJubParams>;
+
+    fn base() -> Point {
+        return ExtendedPoint::from_affine(&curve::BabyJubJubCurve::base_point());
+    }
+
+    #[test]
+    fn test_extended_addition() {
+        let x1 = "17777552123799933955779906779655732241715742912184938656739573121738514868268";
+        let y1 = "2626589144620713026669568689430873010625803728049924121243784502389097019475";
+        let x2 = "16540640123574156134436876038791482806971768689494387082833631921987005038935";
+        let y2 = "20819045374670962167435360035096875258406992893633759881276124905556507972311";
+        let x3 = "7916061937171219682591368294088513039687205273691143098332585753343424131937";
+        let y3 = "14035240266687799601661095864649209771790948434046947201833777492504781204499";
+        let e = Arithmetic::new();
+        let p = ExtendedPoint::from_affine(&curve::BabyJubJubCurveGroup::try_from((x1, y1)).unwrap());
+        let q = ExtendedPoint::from_affine(&curve::BabyJubJubCurveGroup::try_from((x2, y2)).unwrap());
+        let r = curve::BabyJubJubCurveGroup::try_from((x3, y3)).unwrap();
+        assert_eq!(e.add(&p, &q).to_affine() == r, true);
+        assert_eq!(e.add(&p, &q) == e.add(&q, &p), true);
+    }
+
+    #[test]
+    fn test_extended_double() {
+        let e = Arithmetic::new();
+        let b = base();
+        let b2 = e.double(&b);
+        assert_eq!(b2 == e.add(&b, &b), true);
+        // T/Z stays consistent with x·y
+        assert_eq!(b2.x * b2.y == b2.t * b2.z, true);
+        assert_eq!(e.double(&Point::identity()) == Point::identity(), true);
+    }
+
+    #[test]
+    fn test_extended_identity() {
+        let e = Arithmetic::new();
+        let b = base();
+        let o = Point::identity();
+        assert_eq!(e.add(&b, &o) == b, true);
+        assert_eq!(e.add(&o, &b) == b, true);
+        assert_eq!(o.to_affine().is_zero(), true);
+        let nb = ExtendedPoint::from_affine(&-b.to_affine());
+        assert_eq!(nb == -b, true);
+        assert_eq!(e.add(&b, &nb) == o, true);
+    }
+
+    #[test]
+    fn test_extended_mul() {
+        let e = Arithmetic::new();
+        let b = base();
+        let mut acc = Point::identity();
+        for k in 0u32..20 {
+            assert_eq!(e.mul(&b, &BigUint::from(k)) == acc, true);
+            acc = e.add(&acc, &b);
+        }
+        let l = BigUint::from_slice(&curve::BABY_JUBJUB_L);
+        assert_eq!(e.mul(&b, &l) == Point::identity(), true);
+        assert_eq!(e.mul(&b, &(l.clone() + 1u32)) == b, true);
+        assert_eq!(e.mul(&b, &(l - 1u32)) == -b, true);
+    }
+
+    #[test]
+    fn test_extended_mul_ct() {
+        let e = Arithmetic::new();
+        let b = base();
+        let l = BigUint::from_slice(&curve::BABY_JUBJUB_L);
+        for k in [BigUint::from(0u32), BigUint::from(1u32), BigUint::from(8u32), l.clone() - 1u32, l.clone()].iter() {
+            let mut buf = [0u8; 32];
+            let le = k.to_bytes_le();
+            buf[..le.len()].copy_from_slice(&le);
+            assert_eq!(e.mul_ct(&b, &buf) == e.mul(&b, k), true);
+        }
+        // all 256 bits are used, not only the low 251
+        let buf = [0xffu8; 32];
+        let k = BigUint::from_bytes_le(&buf);
+        assert_eq!(e.mul_ct(&b, &buf) == e.mul(&b, &k), true);
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let e = Arithmetic::new();
+        let b = base();
+        let table = e.fixed_base_table(&b, 256, 6);
+        assert_eq!(table.bits() >= 256, true);
+        let l = BigUint::from_slice(&curve::BABY_JUBJUB_L);
+        for k in [BigUint::from(0u32), BigUint::from(63u32), BigUint::from(64u32), l.clone() - 1u32, l].iter() {
+            assert_eq!(e.mul_fixed_base(&table, k) == e.mul(&b, k), true);
+        }
+        // b has Z = 1
+        assert_eq!(e.add_mixed(&e.double(&b), &b) == e.add(&e.double(&b), &b), true);
+        let small = e.fixed_base_table(&b, 10, 3);
+        assert_eq!(e.mul_fixed_base(&small, &BigUint::from(1000u32)) == e.mul(&b, &BigUint::from(1000u32)), true);
+    }
+
+    #[test]
+    fn test_params() {
+        let g = curve::BabyJubJubCurve::generator();
+        assert_eq!(g.is_on_curve(), true);
+        assert_eq!(curve::BabyJubJubCurve::is_in_prime_subgroup(&g), false);
+        let b = curve::BabyJubJubCurve::base_point();
+        assert_eq!(b == curve::BabyJubJubCurve::clear_cofactor(&g), true);
+        assert_eq!(curve::BabyJubJubCurve::is_in_prime_subgroup(&b), true);
+        let n = curve::BabyJubJubCurve::order() * curve::BabyJubJubCurve::cofactor();
+        assert_eq!((g * n).is_zero(), true);
+        // built once
+        let e = arithmetic::<curve::BabyJubJubParams>();
+        assert_eq!(std::ptr::eq(e, arithmetic::<curve::BabyJubJubParams>()), true);
+        assert_eq!(e.a == Arithmetic::new().a && e.d == Arithmetic::new().d, true);
+    }
+
+    fn bench_scalar() -> BigUint {
+        return BigUint::from_slice(&curve::BABY_JUBJUB_L) - 1u32;
+    }
+
+    /// The same extended coordinates as `bench_extended_wnaf`, bit by bit
+    #[bench]
+    fn bench_extended_double_and_add(bench: &mut Bencher) {
+        let e = Arithmetic::new();
+        let b = Summand { p: base(), e: &e };
+        let o = Summand {
+            p: Point::identity(),
+            e: &e,
+        };
+        let k = bench_scalar();
+        bench.iter(|| double_and_add_algorithm(k.clone(), b.clone(), o.clone()).p);
+    }
+
+    #[bench]
+    fn bench_extended_wnaf(bench: &mut Bencher) {
+        let e = Arithmetic::new();
+        let b = base();
+        let k = bench_scalar();
+        bench.iter(|| e.mul(&b, &k));
+    }
+}
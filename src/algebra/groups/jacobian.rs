@@ -0,0 +1,342 @@
+use crate::algebra::fields::arithmetic::batch_inversion;
+use crate::algebra::groups::arithmetic::FixedBaseTable;
+use crate::algebra::traits::MulInv;
+use num::traits::{One, Zero};
+use num_bigint::BigUint;
+use std::ops::{Mul, Sub};
+
+/// Field element operations needed by the Jacobian group law.
+pub trait JacobianField = Clone + PartialEq + Zero + One + Sub<Output = Self> + Mul<Output = Self> + MulInv<Output = Self>;
+
+/// Point on a short Weierstrass curve y^2 = x^3 + ax + b in Jacobian
+/// coordinates (X:Y:Z), x = X/Z^2 and y = Y/Z^3. Z = 0 is the point at infinity.
+/// Group operations are inversion free, only `to_affine` inverts.
+#[derive(Debug, Clone, Copy)]
+pub struct JacobianPoint<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: JacobianField> JacobianPoint<T> {
+    /// (1 : 1 : 0)
+    pub fn infinity() -> Self {
+        return JacobianPoint {
+            x: T::one(),
+            y: T::one(),
+            z: T::zero(),
+        };
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        return self.z.is_zero();
+    }
+
+    /// (x : y : 1), `None` is the point at infinity.
+    pub fn from_affine(p: Option<(T, T)>) -> Self {
+        return match p {
+            Some((x, y)) => JacobianPoint {
+                x: x,
+                y: y,
+                z: T::one(),
+            },
+            None => Self::infinity(),
+        };
+    }
+
+    /// (X/Z^2, Y/Z^3), `None` for the point at infinity.
+    pub fn to_affine(&self) -> Option<(T, T)> {
+        if self.is_infinity() {
+            return None;
+        }
+        let zinv = self.z.clone().mul_inv();
+        let zinv2 = zinv.clone() * zinv.clone();
+        return Some((self.x.clone() * zinv2.clone(), self.y.clone() * zinv2 * zinv));
+    }
+}
+
+impl<T: JacobianField> PartialEq for JacobianPoint<T> {
+    /// X1·Z2^2 = X2·Z1^2 and Y1·Z2^3 = Y2·Z1^3
+    fn eq(&self, rhs: &Self) -> bool {
+        if self.is_infinity() || rhs.is_infinity() {
+            return self.is_infinity() && rhs.is_infinity();
+        }
+        let z1z1 = self.z.clone() * self.z.clone();
+        let z2z2 = rhs.z.clone() * rhs.z.clone();
+        return self.x.clone() * z2z2.clone() == rhs.x.clone() * z1z1.clone()
+            && self.y.clone() * z2z2 * rhs.z.clone() == rhs.y.clone() * z1z1 * self.z.clone();
+    }
+}
+
+fn double_of<T: JacobianField>(v: T) -> T {
+    return v.clone() + v;
+}
+
+/// 2P. With a = 0 this is dbl-2009-l (2M + 5S), otherwise dbl-2007-bl (1M + 8S + 1D).
+/// # ref:
+/// * https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html
+pub fn jacobian_double<T: JacobianField>(p: &JacobianPoint<T>, a: &T) -> JacobianPoint<T> {
+    if p.is_infinity() || p.y.is_zero() {
+        return JacobianPoint::infinity();
+    }
+    let xx = p.x.clone() * p.x.clone();
+    let yy = p.y.clone() * p.y.clone();
+    let yyyy = yy.clone() * yy.clone();
+    let yyyy8 = double_of(double_of(double_of(yyyy.clone())));
+    if a.is_zero() {
+        let xb = p.x.clone() + yy;
+        let d = double_of(xb.clone() * xb - xx.clone() - yyyy);
+        let e = double_of(xx.clone()) + xx;
+        let x3 = e.clone() * e.clone() - double_of(d.clone());
+        let y3 = e * (d - x3.clone()) - yyyy8;
+        let z3 = double_of(p.y.clone() * p.z.clone());
+        return JacobianPoint { x: x3, y: y3, z: z3 };
+    }
+    let zz = p.z.clone() * p.z.clone();
+    let xb = p.x.clone() + yy.clone();
+    let s = double_of(xb.clone() * xb - xx.clone() - yyyy);
+    let m = double_of(xx.clone()) + xx + a.clone() * zz.clone() * zz.clone();
+    let x3 = m.clone() * m.clone() - double_of(s.clone());
+    let y3 = m * (s - x3.clone()) - yyyy8;
+    let yz = p.y.clone() + p.z.clone();
+    let z3 = yz.clone() * yz - yy - zz;
+    return JacobianPoint { x: x3, y: y3, z: z3 };
+}
+
+/// P + Q for Jacobian P and affine Q, madd-2007-bl (7M + 4S).
+pub fn jacobian_add_mixed<T: JacobianField>(p: &JacobianPoint<T>, q: &(T, T), a: &T) -> JacobianPoint<T> {
+    if p.is_infinity() {
+        return JacobianPoint::from_affine(Some(q.clone()));
+    }
+    let (x2, y2) = q;
+    let z1z1 = p.z.clone() * p.z.clone();
+    let u2 = x2.clone() * z1z1.clone();
+    let s2 = y2.clone() * p.z.clone() * z1z1.clone();
+    let h = u2 - p.x.clone();
+    let r = double_of(s2 - p.y.clone());
+    if h.is_zero() {
+        if r.is_zero() {
+            return jacobian_double(p, a);
+        }
+        return JacobianPoint::infinity();
+    }
+    let hh = h.clone() * h.clone();
+    let i = double_of(double_of(hh.clone()));
+    let j = h.clone() * i.clone();
+    let v = p.x.clone() * i;
+    let x3 = r.clone() * r.clone() - j.clone() - double_of(v.clone());
+    let y3 = r * (v - x3.clone()) - double_of(p.y.clone() * j);
+    let zh = p.z.clone() + h;
+    let z3 = zh.clone() * zh - z1z1 - hh;
+    return JacobianPoint { x: x3, y: y3, z: z3 };
+}
+
+/// P + Q for Jacobian P and Q, add-2007-bl (11M + 5S).
+pub fn jacobian_add<T: JacobianField>(p: &JacobianPoint<T>, q: &JacobianPoint<T>, a: &T) -> JacobianPoint<T> {
+    if p.is_infinity() {
+        return q.clone();
+    }
+    if q.is_infinity() {
+        return p.clone();
+    }
+    let z1z1 = p.z.clone() * p.z.clone();
+    let z2z2 = q.z.clone() * q.z.clone();
+    let u1 = p.x.clone() * z2z2.clone();
+    let u2 = q.x.clone() * z1z1.clone();
+    let s1 = p.y.clone() * q.z.clone() * z2z2.clone();
+    let s2 = q.y.clone() * p.z.clone() * z1z1.clone();
+    let h = u2 - u1.clone();
+    let r = double_of(s2 - s1.clone());
+    if h.is_zero() {
+        if r.is_zero() {
+            return jacobian_double(p, a);
+        }
+        return JacobianPoint::infinity();
+    }
+    let i = double_of(h.clone());
+    let i = i.clone() * i;
+    let j = h.clone() * i.clone();
+    let v = u1 * i;
+    let x3 = r.clone() * r.clone() - j.clone() - double_of(v.clone());
+    let y3 = r * (v - x3.clone()) - double_of(s1 * j);
+    let zz = p.z.clone() + q.z.clone();
+    let z3 = (zz.clone() * zz - z1z1 - z2z2) * h;
+    return JacobianPoint { x: x3, y: y3, z: z3 };
+}
+
+/// k·P for affine P, left to right double and add with mixed additions,
+/// the caller normalizes the result once.
+pub fn jacobian_scalar_mul<T: JacobianField>(p: &Option<(T, T)>, k: &BigUint, a: &T) -> JacobianPoint<T> {
+    let q = match p {
+        Some(q) => q,
+        None => return JacobianPoint::infinity(),
+    };
+    let mut r = JacobianPoint::infinity();
+    for bit in k.to_radix_le(2).iter().rev() {
+        r = jacobian_double(&r, a);
+        if *bit == 1 {
+            r = jacobian_add_mixed(&r, q, a);
+        }
+    }
+    return r;
+}
+
+/// Table of multiples of the affine point p for scalars of up to `bits` bits,
+/// in windows of w bits. The entries are normalized to affine with a single
+/// shared inversion, so each lookup is one mixed addition.
+pub fn jacobian_fixed_base_table<T: JacobianField>(
+    p: &(T, T),
+    a: &T,
+    bits: usize,
+    w: usize,
+) -> FixedBaseTable<Option<(T, T)>> {
+    let table = FixedBaseTable::new(
+        &JacobianPoint::from_affine(Some(p.clone())),
+        bits,
+        w,
+        |q, r| jacobian_add(q, r, a),
+        |q| jacobian_double(q, a),
+    );
+    return table.map_entries(|points| {
+        let mut zinv = points.iter().map(|q| q.z.clone()).collect::<Vec<T>>();
+        batch_inversion(&mut zinv);
+        return points
+            .iter()
+            .zip(zinv.iter())
+            .map(|(q, zi)| {
+                if q.is_infinity() {
+                    return None;
+                }
+                let zi2 = zi.clone() * zi.clone();
+                return Some((q.x.clone() * zi2.clone(), q.y.clone() * zi2 * zi.clone()));
+            })
+            .collect();
+    });
+}
+
+/// k·P from the table of P, k must fit in `table.bits()` bits.
+/// Variable time, see `FixedBaseTable::mul`.
+pub fn jacobian_mul_fixed_base<T: JacobianField>(
+    table: &FixedBaseTable<Option<(T, T)>>,
+    k: &BigUint,
+    a: &T,
+) -> JacobianPoint<T> {
+    return table.mul(k, JacobianPoint::infinity(), |r, q| match q {
+        Some(q) => jacobian_add_mixed(&r, q, a),
+        None => r,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algebra::groups::arithmetic::{weierstrass_form_curve_op, WeierstrassAffinePoint};
+    use crate::algebra::groups::jacobian::*;
+    use crate::curves::secp256k1::curve::Secp256k1Curve;
+    use crate::curves::secp256k1::ff::{Secp256k1Field, Secp256k1FieldEle};
+    use num::Zero;
+    use num_bigint::BigUint;
+
+    type Fe = Secp256k1FieldEle;
+
+    fn generator() -> (Fe, Fe) {
+        let g = Secp256k1Curve::generator();
+        return (g.x().element(), g.y().element());
+    }
+
+    /// Affine reference, y^2 = x^3 + ax + b
+    fn affine_op(p: (Fe, Fe), q: (Fe, Fe), a: Fe, b: Fe) -> (Fe, Fe) {
+        fn boxed(v: Fe) -> Secp256k1Field {
+            return box v;
+        }
+        let p: WeierstrassAffinePoint<Fe> = Some((boxed(p.0), boxed(p.1)));
+        let q: WeierstrassAffinePoint<Fe> = Some((boxed(q.0), boxed(q.1)));
+        let zero = Secp256k1Field::zero();
+        let (x, y) = weierstrass_form_curve_op(p, q, zero.clone(), zero.clone(), zero, boxed(a), boxed(b)).unwrap();
+        return (x.element(), y.element());
+    }
+
+    #[test]
+    fn test_jacobian_roundtrip() {
+        let g = generator();
+        let j = JacobianPoint::from_affine(Some(g));
+        assert_eq!(j.to_affine() == Some(g), true);
+        assert_eq!(JacobianPoint::<Fe>::infinity().to_affine().is_none(), true);
+        // (λ^2 X : λ^3 Y : λ Z) is the same point
+        let l = Fe::from(12345u32);
+        let s = JacobianPoint {
+            x: g.0 * l * l,
+            y: g.1 * l * l * l,
+            z: l,
+        };
+        assert_eq!(s == j, true);
+        assert_eq!(s.to_affine() == Some(g), true);
+    }
+
+    #[test]
+    fn test_jacobian_double() {
+        let g = generator();
+        let a = Fe::zero();
+        let b = Fe::from(7u32);
+        let g2 = jacobian_double(&JacobianPoint::from_affine(Some(g)), &a);
+        assert_eq!(g2.to_affine() == Some(affine_op(g, g, a, b)), true);
+        assert_eq!(jacobian_double(&JacobianPoint::<Fe>::infinity(), &a).is_infinity(), true);
+
+        // the general formula, G also lies on y^2 = x^3 + 3x + b' for a suitable b'
+        let a = Fe::from(3u32);
+        let b = g.1 * g.1 - g.0 * g.0 * g.0 - a * g.0;
+        let g2 = jacobian_double(&JacobianPoint::from_affine(Some(g)), &a);
+        let expected = affine_op(g, g, a, b);
+        assert_eq!(g2.to_affine() == Some(expected), true);
+        let g4 = jacobian_double(&g2, &a);
+        assert_eq!(g4.to_affine() == Some(affine_op(expected, expected, a, b)), true);
+    }
+
+    #[test]
+    fn test_jacobian_add() {
+        let g = generator();
+        let a = Fe::zero();
+        let j = JacobianPoint::from_affine(Some(g));
+        let g2 = jacobian_double(&j, &a);
+        let g3 = jacobian_add_mixed(&g2, &g, &a);
+        assert_eq!(g3.to_affine() == Some(affine_op(g2.to_affine().unwrap(), g, a, Fe::from(7u32))), true);
+        assert_eq!(g3 == jacobian_add(&g2, &j, &a), true);
+        assert_eq!(g3 == jacobian_add(&j, &g2, &a), true);
+        let g5 = jacobian_add(&g3, &g2, &a);
+        assert_eq!(g5 == jacobian_add_mixed(&jacobian_double(&g2, &a), &g, &a), true);
+        // P + P doubles, P + (-P) is infinity
+        assert_eq!(jacobian_add_mixed(&j, &g, &a) == g2, true);
+        assert_eq!(jacobian_add(&j, &j, &a) == g2, true);
+        assert_eq!(jacobian_add_mixed(&j, &(g.0, -g.1), &a).is_infinity(), true);
+        assert_eq!(jacobian_add(&JacobianPoint::infinity(), &j, &a) == j, true);
+        assert_eq!(jacobian_add_mixed(&JacobianPoint::infinity(), &g, &a) == j, true);
+    }
+
+    #[test]
+    fn test_jacobian_scalar_mul() {
+        let g = generator();
+        let a = Fe::zero();
+        let mut expected = JacobianPoint::infinity();
+        for k in 0u32..20 {
+            assert_eq!(jacobian_scalar_mul(&Some(g), &BigUint::from(k), &a) == expected, true);
+            expected = jacobian_add_mixed(&expected, &g, &a);
+        }
+        assert_eq!(jacobian_scalar_mul(&None, &BigUint::from(5u32), &a).is_infinity(), true);
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let g = generator();
+        let a = Fe::zero();
+        let table = jacobian_fixed_base_table(&g, &a, 256, 6);
+        assert_eq!(table.bits() >= 256, true);
+        let k = BigUint::parse_bytes(b"e7a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f", 16).unwrap();
+        assert_eq!(jacobian_mul_fixed_base(&table, &k, &a) == jacobian_scalar_mul(&Some(g), &k, &a), true);
+        for k in 0u32..70 {
+            let k = BigUint::from(k);
+            assert_eq!(jacobian_mul_fixed_base(&table, &k, &a) == jacobian_scalar_mul(&Some(g), &k, &a), true);
+        }
+        let small = jacobian_fixed_base_table(&g, &a, 10, 3);
+        let k = BigUint::from(1000u32);
+        assert_eq!(jacobian_mul_fixed_base(&small, &k, &a) == jacobian_scalar_mul(&Some(g), &k, &a), true);
+    }
+}
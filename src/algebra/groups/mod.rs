@@ -1,2 +1,6 @@
 pub mod arithmetic;
 pub mod ecg;
+pub mod edwards;
+pub mod jacobian;
+pub mod projective;
+pub mod weierstrass;
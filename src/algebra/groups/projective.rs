@@ -0,0 +1,165 @@
+use crate::algebra::groups::jacobian::JacobianField;
+use crate::algebra::traits::ConditionalSwap;
+
+/// Point on a short Weierstrass curve y^2 = x^3 + b (a = 0) in homogeneous
+/// projective coordinates (X:Y:Z), x = X/Z and y = Y/Z, the point at
+/// infinity is (0:1:0).
+/// The group law below is complete on prime order curves: the same formula
+/// handles doubling, the identity and inverses, without branches, which makes
+/// it suitable for constant time scalar multiplication.
+/// # ref:
+/// * Renes, Costello, Batina, Complete addition formulas for prime order elliptic curves, 2016
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectivePoint<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: JacobianField> ProjectivePoint<T> {
+    pub fn identity() -> Self {
+        return ProjectivePoint {
+            x: T::zero(),
+            y: T::one(),
+            z: T::zero(),
+        };
+    }
+
+    /// (x : y : 1), `None` is the point at infinity.
+    pub fn from_affine(p: Option<(T, T)>) -> Self {
+        return match p {
+            Some((x, y)) => ProjectivePoint {
+                x: x,
+                y: y,
+                z: T::one(),
+            },
+            None => Self::identity(),
+        };
+    }
+
+    /// (X/Z, Y/Z), `None` for the point at infinity.
+    pub fn to_affine(&self) -> Option<(T, T)> {
+        if self.z.is_zero() {
+            return None;
+        }
+        let zinv = self.z.clone().mul_inv();
+        return Some((self.x.clone() * zinv.clone(), self.y.clone() * zinv));
+    }
+}
+
+impl<T: ConditionalSwap> ConditionalSwap for ProjectivePoint<T> {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: u8) {
+        T::conditional_swap(&mut a.x, &mut b.x, choice);
+        T::conditional_swap(&mut a.y, &mut b.y, choice);
+        T::conditional_swap(&mut a.z, &mut b.z, choice);
+    }
+}
+
+/// P + Q with a = 0 and b3 = 3b, Algorithm 7 (12M + 2m3b).
+pub fn complete_add<T: JacobianField>(p: &ProjectivePoint<T>, q: &ProjectivePoint<T>, b3: &T) -> ProjectivePoint<T> {
+    let t0 = p.x.clone() * q.x.clone();
+    let t1 = p.y.clone() * q.y.clone();
+    let t2 = p.z.clone() * q.z.clone();
+    let t3 = (p.x.clone() + p.y.clone()) * (q.x.clone() + q.y.clone());
+    let t4 = t0.clone() + t1.clone();
+    let t3 = t3 - t4;
+    let t4 = (p.y.clone() + p.z.clone()) * (q.y.clone() + q.z.clone());
+    let x3 = t1.clone() + t2.clone();
+    let t4 = t4 - x3;
+    let x3 = (p.x.clone() + p.z.clone()) * (q.x.clone() + q.z.clone());
+    let y3 = t0.clone() + t2.clone();
+    let y3 = x3 - y3;
+    let x3 = t0.clone() + t0.clone();
+    let t0 = x3 + t0;
+    let t2 = b3.clone() * t2;
+    let z3 = t1.clone() + t2.clone();
+    let t1 = t1 - t2;
+    let y3 = b3.clone() * y3;
+    let x3 = t4.clone() * y3.clone();
+    let t2 = t3.clone() * t1.clone();
+    let x3 = t2 - x3;
+    let y3 = y3 * t0.clone();
+    let t1 = t1 * z3.clone();
+    let y3 = t1 + y3;
+    let t0 = t0 * t3;
+    let z3 = z3 * t4;
+    let z3 = z3 + t0;
+    return ProjectivePoint { x: x3, y: y3, z: z3 };
+}
+
+/// 2P with a = 0 and b3 = 3b, Algorithm 9 (6M + 2S + 1m3b).
+pub fn complete_double<T: JacobianField>(p: &ProjectivePoint<T>, b3: &T) -> ProjectivePoint<T> {
+    let t0 = p.y.clone() * p.y.clone();
+    let z3 = t0.clone() + t0.clone();
+    let z3 = z3.clone() + z3;
+    let z3 = z3.clone() + z3;
+    let t1 = p.y.clone() * p.z.clone();
+    let t2 = p.z.clone() * p.z.clone();
+    let t2 = b3.clone() * t2;
+    let x3 = t2.clone() * z3.clone();
+    let y3 = t0.clone() + t2.clone();
+    let z3 = t1 * z3;
+    let t1 = t2.clone() + t2.clone();
+    let t2 = t1 + t2;
+    let t0 = t0 - t2;
+    let y3 = t0.clone() * y3;
+    let y3 = x3 + y3;
+    let t1 = p.x.clone() * p.y.clone();
+    let x3 = t0 * t1;
+    let x3 = x3.clone() + x3;
+    return ProjectivePoint { x: x3, y: y3, z: z3 };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algebra::groups::jacobian::{jacobian_add_mixed, jacobian_double, JacobianPoint};
+    use crate::algebra::groups::projective::*;
+    use crate::curves::secp256k1::curve::Secp256k1Curve;
+    use crate::curves::secp256k1::ff::Secp256k1FieldEle;
+    use num::Zero;
+
+    type Fe = Secp256k1FieldEle;
+
+    fn generator() -> (Fe, Fe) {
+        let g = Secp256k1Curve::generator();
+        return (g.x().element(), g.y().element());
+    }
+
+    #[test]
+    fn test_complete_formulas() {
+        let b3 = Fe::from(21u32);
+        let a = Fe::zero();
+        let g = generator();
+        let p = ProjectivePoint::from_affine(Some(g));
+        let o = ProjectivePoint::<Fe>::identity();
+
+        let mut j = JacobianPoint::from_affine(Some(g));
+        let mut q = p;
+        for _ in 0..8 {
+            j = jacobian_add_mixed(&j, &g, &a);
+            q = complete_add(&q, &p, &b3);
+            assert_eq!(q.to_affine() == j.to_affine(), true);
+        }
+        assert_eq!(complete_double(&q, &b3).to_affine() == jacobian_double(&j, &a).to_affine(), true);
+        // the addition formula also doubles
+        assert_eq!(complete_add(&q, &q, &b3).to_affine() == jacobian_double(&j, &a).to_affine(), true);
+
+        assert_eq!(complete_add(&p, &o, &b3).to_affine() == Some(g), true);
+        assert_eq!(complete_add(&o, &p, &b3).to_affine() == Some(g), true);
+        assert_eq!(complete_add(&o, &o, &b3).to_affine().is_none(), true);
+        assert_eq!(complete_double(&o, &b3).to_affine().is_none(), true);
+        let n = ProjectivePoint::from_affine(Some((g.0, -g.1)));
+        assert_eq!(complete_add(&p, &n, &b3).to_affine().is_none(), true);
+    }
+
+    #[test]
+    fn test_conditional_swap() {
+        let g = ProjectivePoint::from_affine(Some(generator()));
+        let o = ProjectivePoint::<Fe>::identity();
+        let (mut a, mut b) = (g, o);
+        ProjectivePoint::conditional_swap(&mut a, &mut b, 0);
+        assert_eq!(a.to_affine() == Some(generator()) && b.to_affine().is_none(), true);
+        ProjectivePoint::conditional_swap(&mut a, &mut b, 1);
+        assert_eq!(b.to_affine() == Some(generator()) && a.to_affine().is_none(), true);
+    }
+}
@@ -0,0 +1,328 @@
+use crate::algebra::fields::prime;
+use crate::algebra::groups::arithmetic::{msm_algorithm, weierstrass_form_curve_op, WeierstrassAffinePoint};
+use crate::algebra::groups::ecg;
+use crate::algebra::groups::jacobian::{jacobian_add, jacobian_scalar_mul, JacobianPoint};
+use num::Zero;
+use num_bigint::BigUint;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Add;
+
+/// Constants of a short Weierstrass curve y^2 = x^3 + ax + b over `Fe`, as
+/// little endian u32 limbs. Implementing this is all a new curve needs:
+/// `ShortWeierstrassCurve` provides the group law on top.
+pub trait ShortWeierstrassParams: 'static + Sized + Copy + fmt::Debug {
+    type Fe: ecg::CurveField;
+    const A: &'static [u32];
+    const B: &'static [u32];
+    /// Standard generator of the curve
+    const GENERATOR_X: &'static [u32];
+    const GENERATOR_Y: &'static [u32];
+    /// Order n of the prime order subgroup
+    const ORDER: &'static [u32];
+    /// The curve order is h·n
+    const COFACTOR: u32;
+
+    /// k·P for `Scalar` and `Mul`, double and add in Jacobian coordinates
+    /// unless the curve has something faster, e.g. an endomorphism.
+    fn scalar_mul(p: &ShortWeierstrassGroup<Self>, k: &BigUint) -> ShortWeierstrassGroup<Self> {
+        return ShortWeierstrassCurve::<Self>::mul_jacobian(p, k);
+    }
+}
+
+/// Affine point of the curve given by `P`, or the point at infinity when
+/// `infinity` is set, in which case the coordinates are ignored.
+#[derive(Debug, Clone)]
+pub struct ShortWeierstrassCurve<P: ShortWeierstrassParams> {
+    pub x: prime::BoxedPrimeField<P::Fe>,
+    pub y: prime::BoxedPrimeField<P::Fe>,
+    pub infinity: bool,
+    params: PhantomData<P>,
+}
+
+pub type ShortWeierstrassGroup<P> =
+    ecg::EllipticCurveGroup<<P as ShortWeierstrassParams>::Fe, ShortWeierstrassCurve<P>>;
+
+impl<P: ShortWeierstrassParams> ShortWeierstrassCurve<P> {
+    /// (x, y), not checked to be on the curve.
+    pub fn new(x: prime::BoxedPrimeField<P::Fe>, y: prime::BoxedPrimeField<P::Fe>) -> ShortWeierstrassGroup<P> {
+        return box ShortWeierstrassCurve {
+            x: x,
+            y: y,
+            infinity: false,
+            params: PhantomData,
+        };
+    }
+
+    /// The standard generator G.
+    pub fn generator() -> ShortWeierstrassGroup<P> {
+        return ecg::from_biguint_unchecked::<P::Fe, Self>(
+            BigUint::from_slice(P::GENERATOR_X),
+            BigUint::from_slice(P::GENERATOR_Y),
+        );
+    }
+
+    /// Order n of the prime order subgroup, the curve has h·n points.
+    pub fn order() -> BigUint {
+        return BigUint::from_slice(P::ORDER);
+    }
+
+    /// h
+    pub fn cofactor() -> BigUint {
+        return BigUint::from(P::COFACTOR);
+    }
+
+    /// a in Montgomery form, converted once.
+    pub(crate) fn a() -> P::Fe {
+        return *ecg::per_type::<P, P::Fe>(|| <P::Fe as From<BigUint>>::from(BigUint::from_slice(P::A)));
+    }
+
+    fn affine(p: &ShortWeierstrassGroup<P>) -> Option<(P::Fe, P::Fe)> {
+        if p.is_infinity() {
+            return None;
+        }
+        return Some((
+            p.x().element(),
+            p.y().element(),
+        ));
+    }
+
+    pub fn to_jacobian(p: &ShortWeierstrassGroup<P>) -> JacobianPoint<P::Fe> {
+        return JacobianPoint::from_affine(Self::affine(p));
+    }
+
+    /// Normalizes back to affine, one inversion.
+    pub fn from_jacobian(p: &JacobianPoint<P::Fe>) -> ShortWeierstrassGroup<P> {
+        return Self::from_affine(p.to_affine());
+    }
+
+    /// `None` is the point at infinity.
+    pub fn from_affine(p: Option<(P::Fe, P::Fe)>) -> ShortWeierstrassGroup<P> {
+        return match p {
+            Some((x, y)) => {
+                let x: prime::BoxedPrimeField<P::Fe> = box x;
+                let y: prime::BoxedPrimeField<P::Fe> = box y;
+                Self::new(x, y)
+            }
+            None => <Self as ecg::Op<P::Fe, ShortWeierstrassCurve<P>>>::identity(),
+        };
+    }
+
+    /// Double and add in Jacobian coordinates, normalized once at the end.
+    pub fn mul_jacobian(p: &ShortWeierstrassGroup<P>, k: &BigUint) -> ShortWeierstrassGroup<P> {
+        return Self::from_jacobian(&jacobian_scalar_mul(&Self::affine(p), k, &Self::a()));
+    }
+}
+
+/// Jacobian point paired with a, gives the generic multi-scalar
+/// multiplication algorithms the group law as `Add`.
+#[derive(Debug, Clone)]
+pub(crate) struct Summand<'a, P: ShortWeierstrassParams> {
+    pub(crate) p: JacobianPoint<P::Fe>,
+    pub(crate) a: &'a P::Fe,
+}
+
+impl<'a, P: ShortWeierstrassParams> Add for Summand<'a, P> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        return Summand {
+            p: jacobian_add(&self.p, &rhs.p, self.a),
+            a: self.a,
+        };
+    }
+}
+
+impl<P: ShortWeierstrassParams> ecg::FromBigUint<P::Fe, ShortWeierstrassCurve<P>> for ShortWeierstrassCurve<P> {
+    fn from(x: prime::BoxedPrimeField<P::Fe>, y: prime::BoxedPrimeField<P::Fe>) -> ShortWeierstrassGroup<P> {
+        return Self::new(x, y);
+    }
+}
+
+impl<P: ShortWeierstrassParams> ecg::Op<P::Fe, ShortWeierstrassCurve<P>> for ShortWeierstrassCurve<P> {
+    fn op(a: ShortWeierstrassGroup<P>, b: ShortWeierstrassGroup<P>) -> ShortWeierstrassGroup<P> {
+        fn affine<P: ShortWeierstrassParams>(p: ShortWeierstrassGroup<P>) -> WeierstrassAffinePoint<P::Fe> {
+            if p.is_infinity() {
+                return None;
+            }
+            return Some((p.x(), p.y()));
+        }
+
+        return match weierstrass_form_curve_op(
+            affine(a),
+            affine(b),
+            prime::BoxedPrimeField::zero(),
+            prime::BoxedPrimeField::zero(),
+            prime::BoxedPrimeField::zero(),
+            prime::BoxedPrimeField::from(P::A),
+            prime::BoxedPrimeField::from(P::B),
+        ) {
+            Some((x, y)) => Self::new(x, y),
+            None => Self::identity(),
+        };
+    }
+
+    fn identity() -> ShortWeierstrassGroup<P> {
+        return box ShortWeierstrassCurve {
+            x: prime::BoxedPrimeField::zero(),
+            y: prime::BoxedPrimeField::zero(),
+            infinity: true,
+            params: PhantomData,
+        };
+    }
+
+    /// -(x, y) = (x, -y)
+    fn negate(a: ShortWeierstrassGroup<P>) -> ShortWeierstrassGroup<P> {
+        if a.is_infinity() {
+            return a;
+        }
+        return Self::new(a.x(), -a.y());
+    }
+
+    /// `ShortWeierstrassParams::scalar_mul`
+    fn scalar_mul(a: ShortWeierstrassGroup<P>, k: BigUint) -> ShortWeierstrassGroup<P> {
+        return P::scalar_mul(&a, &k);
+    }
+
+    /// Straus or Pippenger in Jacobian coordinates, normalized once at the end.
+    fn msm(p: &[ShortWeierstrassGroup<P>], k: &[BigUint]) -> ShortWeierstrassGroup<P> {
+        let a = Self::a();
+        let p = p
+            .iter()
+            .map(|p| Summand {
+                p: Self::to_jacobian(p),
+                a: &a,
+            })
+            .collect::<Vec<Summand<P>>>();
+        let init = Summand {
+            p: JacobianPoint::infinity(),
+            a: &a,
+        };
+        return Self::from_jacobian(&msm_algorithm(&p, k, init).p);
+    }
+}
+
+impl<P: ShortWeierstrassParams> ecg::CurvePoint<P::Fe, ShortWeierstrassCurve<P>> for ShortWeierstrassCurve<P> {
+    fn x(&self) -> prime::BoxedPrimeField<P::Fe> {
+        return self.x.clone();
+    }
+    fn y(&self) -> prime::BoxedPrimeField<P::Fe> {
+        return self.y.clone();
+    }
+    /// y^2 = x^3 + ax + b, the point at infinity is on the curve
+    fn is_on_curve(&self) -> bool {
+        if self.infinity {
+            return true;
+        }
+        let x = self.x();
+        return self.y() * self.y()
+            == x.clone() * x.clone() * x.clone()
+                + prime::BoxedPrimeField::<P::Fe>::from(P::A) * x
+                + prime::BoxedPrimeField::<P::Fe>::from(P::B);
+    }
+    fn is_infinity(&self) -> bool {
+        return self.infinity;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algebra::groups::arithmetic::double_and_add_algorithm;
+    use crate::algebra::groups::ecg;
+    use crate::algebra::groups::weierstrass::*;
+    use crate::algebra::traits::Scalar;
+    use crate::curves::secp256k1::{curve, ff};
+    use crate::prime_field;
+    use num::Zero;
+    use num_bigint::BigUint;
+    use std::convert::TryFrom;
+
+    prime_field!(F10007 = 10007);
+
+    /// y^2 = x^3 + 2x + 3 over F_10007, of order 18·547
+    #[derive(Debug, Clone, Copy)]
+    struct SmallParams;
+
+    impl ShortWeierstrassParams for SmallParams {
+        type Fe = F10007Ele;
+        const A: &'static [u32] = &[2];
+        const B: &'static [u32] = &[3];
+        const GENERATOR_X: &'static [u32] = &[9745];
+        const GENERATOR_Y: &'static [u32] = &[5813];
+        const ORDER: &'static [u32] = &[547];
+        const COFACTOR: u32 = 18;
+    }
+
+    /// secp256k1 without the GLV `scalar_mul`, so the generic code runs on
+    /// a real curve.
+    #[derive(Debug, Clone, Copy)]
+    struct GenericSecp256k1Params;
+
+    impl ShortWeierstrassParams for GenericSecp256k1Params {
+        type Fe = ff::Secp256k1FieldEle;
+        const A: &'static [u32] = &[curve::SECP256K1_A];
+        const B: &'static [u32] = &[curve::SECP256K1_B];
+        const GENERATOR_X: &'static [u32] = &curve::SECP256K1_GX;
+        const GENERATOR_Y: &'static [u32] = &curve::SECP256K1_GY;
+        const ORDER: &'static [u32] = &curve::SECP256K1_N;
+        const COFACTOR: u32 = curve::SECP256K1_COFACTOR;
+    }
+
+    type Small = ShortWeierstrassGroup<SmallParams>;
+
+    fn generator<P: ShortWeierstrassParams>() -> ShortWeierstrassGroup<P> {
+        return ShortWeierstrassCurve::<P>::generator();
+    }
+
+    #[test]
+    fn test_group_law() {
+        let g = generator::<SmallParams>();
+        let o = Small::zero();
+        assert_eq!(g.is_on_curve(), true);
+        assert_eq!(g.clone() + g.clone() == Small::try_from((930u32, 1529u32)).unwrap(), true);
+        assert_eq!(g.clone().scalar(100usize) == Small::try_from((2208u32, 86u32)).unwrap(), true);
+        assert_eq!(g.clone().scalar(546usize) == -g.clone(), true);
+        assert_eq!(g.clone().scalar(546usize) == Small::try_from((9745u32, 4194u32)).unwrap(), true);
+        assert_eq!(g.clone().scalar(ShortWeierstrassCurve::<SmallParams>::order()).is_zero(), true);
+        assert_eq!(ShortWeierstrassCurve::<SmallParams>::cofactor(), BigUint::from(18u32));
+        assert_eq!(g.clone() + o.clone() == g.clone(), true);
+        assert_eq!((g.clone() - g.clone()).is_zero(), true);
+        assert_eq!(o.is_on_curve(), true);
+        assert_eq!(Small::try_from((1u32, 1u32)).is_err(), true);
+    }
+
+    #[test]
+    fn test_scalar_mul() {
+        let g = generator::<SmallParams>();
+        for k in 0usize..40 {
+            let k = BigUint::from(k * 97);
+            let expected = double_and_add_algorithm(k.clone(), g.clone(), Small::zero());
+            assert_eq!(g.clone().scalar(k) == expected, true);
+        }
+    }
+
+    #[test]
+    fn test_msm() {
+        let g = generator::<SmallParams>();
+        let points = (1usize..70).map(|i| g.clone().scalar(i * 7)).collect::<Vec<Small>>();
+        let scalars = (1u32..70).map(|i| BigUint::from(i * 131 + 5)).collect::<Vec<BigUint>>();
+        for n in [0usize, 1, 3, 69].iter() {
+            let expected = points[..*n]
+                .iter()
+                .zip(scalars[..*n].iter())
+                .fold(Small::zero(), |acc, (p, k)| acc + p.clone().scalar(k.clone()));
+            assert_eq!(ecg::msm(&points[..*n], &scalars[..*n]) == expected, true);
+        }
+    }
+
+    #[test]
+    fn test_matches_secp256k1() {
+        let g = generator::<GenericSecp256k1Params>();
+        let h = curve::Secp256k1Curve::generator();
+        let k = BigUint::parse_bytes(b"e7a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f", 16).unwrap();
+        let p = g.clone().scalar(k.clone()) + g.clone();
+        let q = h.clone().scalar(k) + h;
+        assert_eq!(p.x() == q.x() && p.y() == q.y(), true);
+        let n = ShortWeierstrassCurve::<GenericSecp256k1Params>::order();
+        assert_eq!(g.scalar(n).is_zero(), true);
+    }
+}
@@ -1,4 +1,6 @@
+use crate::Error;
 use num::traits::{One, Zero};
+use num_bigint::BigUint;
 use std::cmp::Eq;
 /// # ref:
 /// * http://www-users.math.umn.edu/~brubaker/docs/152/152groups.pdf
@@ -49,6 +51,77 @@ pub trait MulInv {
     fn mul_inv(self) -> Self;
 }
 
+/// Swaps a and b when choice is 1 and leaves them alone when it is 0,
+/// in time independent of choice and of the values.
+pub trait ConditionalSwap {
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: u8);
+}
+
+/// Inversion and division that report `Error::NonInvertible` instead of
+/// returning a meaningless value for zero.
+pub trait CheckedInv: Sized {
+    fn checked_inv(&self) -> Result<Self, Error>;
+    fn checked_div(&self, rhs: &Self) -> Result<Self, Error>;
+}
+
+/// Fixed width canonical encoding, the width is the byte length of the modulus.
+/// Decoding rejects values which are not reduced modulo p.
+/// Implementations provide the little endian forms, the rest derives from them.
+pub trait ByteEncoding: Sized {
+    fn to_bytes_le(&self) -> Vec<u8>;
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, Error>;
+
+    fn to_bytes_be(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes_le();
+        bytes.reverse();
+        return bytes;
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, Error> {
+        let mut le = bytes.to_vec();
+        le.reverse();
+        return Self::from_bytes_le(&le);
+    }
+
+    /// Big endian, lower case, without `0x` prefix.
+    fn to_hex(&self) -> String {
+        return self
+            .to_bytes_be()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+    }
+
+    /// Big endian, an optional `0x` prefix is accepted.
+    fn from_hex(s: &str) -> Result<Self, Error> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let digits = s
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(Error::InvalidHex)?;
+        if digits.len() % 2 != 0 {
+            return Err(Error::InvalidHex);
+        }
+        let bytes: Vec<u8> = digits.chunks(2).map(|c| c[0] << 4 | c[1]).collect();
+        return Self::from_bytes_be(&bytes);
+    }
+}
+
+/// Exponentiation by square and multiply.
+pub trait Pow: Sized {
+    fn pow(&self, e: &BigUint) -> Self;
+    fn pow_u64(&self, e: u64) -> Self;
+}
+
+/// Quadratic residuosity and square roots in a prime field.
+pub trait Sqrt: Sized {
+    /// Legendre symbol (a/p): 0 if a = 0, 1 if a is a non-zero square, -1 otherwise.
+    fn legendre(&self) -> i8;
+    fn is_square(&self) -> bool;
+    fn sqrt(&self) -> Option<Self>;
+}
+
 pub trait Scalar<T>: Semigroup {
     type Output;
     fn scalar(self, rhs: T) -> Self;
@@ -1,57 +1,310 @@
 /// DOC: https://eips.ethereum.org/EIPS/eip-2494
+use crate::algebra::groups::arithmetic::FixedBaseTable;
 use crate::algebra::groups::ecg;
-use crate::curves::baby_jubjub::ff;
+use crate::algebra::groups::edwards::{self, ExtendedPoint, TwistedEdwardsCurve, TwistedEdwardsParams};
+use crate::algebra::traits::{ByteEncoding, CheckedInv, Sqrt};
+use crate::curves::baby_jubjub::{ff, scalar};
+use crate::Error;
 use num::{One, Zero};
+use num_bigint::BigUint;
+use std::convert::TryFrom;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::OnceLock;
 
 pub const BABY_JUBJUB_A: u32 = 168700u32;
 pub const BABY_JUBJUB_B: u32 = 168696u32;
 
-#[derive(Debug, Clone)]
-pub struct BabyJubJubCurve {
-    pub x: ff::BabyJubJubField,
-    pub y: ff::BabyJubJubField,
+/// The curve order is h·l
+pub const BABY_JUBJUB_COFACTOR: u32 = 8u32;
+
+/// Order l of the prime order subgroup
+pub const BABY_JUBJUB_L: [u32; 8] = [
+    0x392126f1u32,
+    0x677297dcu32,
+    0x3920ee0au32,
+    0xab3eedb8u32,
+    0xd0302b0bu32,
+    0x370a08b6u32,
+    0x5c263405u32,
+    0x060c89ceu32,
+];
+
+/// Generator G of the full group, of order h·l
+pub const BABY_JUBJUB_GX: [u32; 8] = [
+    0xf4d4b45eu32,
+    0x40f41a59u32,
+    0x5b1162bbu32,
+    0xb494b125u32,
+    0xf25645adu32,
+    0x38bcba38u32,
+    0x445b673du32,
+    0x023343e3u32,
+];
+
+pub const BABY_JUBJUB_GY: [u32; 8] = [
+    0xfc000001u32,
+    0x50f87d64u32,
+    0x1e6e5c24u32,
+    0x4a0cfa12u32,
+    0xa0605617u32,
+    0x6e14116du32,
+    0xb84c680au32,
+    0x0c19139cu32,
+];
+
+/// Base point B = h·G, generates the prime order subgroup
+pub const BABY_JUBJUB_BX: [u32; 8] = [
+    0xbb957051u32,
+    0x2893f3f6u32,
+    0x0534e0b6u32,
+    0x2ab8d801u32,
+    0x9d6277c1u32,
+    0x4eacb2e0u32,
+    0xd63e739bu32,
+    0x0bb77a6au32,
+];
+
+pub const BABY_JUBJUB_BY: [u32; 8] = [
+    0x872d7d8bu32,
+    0x4b3c257au32,
+    0xb9e13377u32,
+    0xfce0051fu32,
+    0xd16bf9edu32,
+    0x25572e1cu32,
+    0xf7a0b249u32,
+    0x25797203u32,
+];
+
+/// Fixed base tables cover scalars of this many bits in windows of
+/// `FIXED_BASE_WINDOW` bits.
+const FIXED_BASE_BITS: usize = 256;
+const FIXED_BASE_WINDOW: usize = 6;
+
+fn fixed_base(x: &[u32; 8], y: &[u32; 8]) -> FixedBaseTable<ExtendedPoint<BabyJubJubParams>> {
+    let p = ExtendedPoint::from_affine(&ecg::from_biguint_unchecked::<ff::BabyJubJubFieldEle, BabyJubJubCurve>(
+        BigUint::from_slice(x),
+        BigUint::from_slice(y),
+    ));
+    return edwards::arithmetic::<BabyJubJubParams>().fixed_base_table(&p, FIXED_BASE_BITS, FIXED_BASE_WINDOW);
+}
+
+/// Table for G, built on first use.
+fn generator_table() -> &'static FixedBaseTable<ExtendedPoint<BabyJubJubParams>> {
+    static TABLE: OnceLock<FixedBaseTable<ExtendedPoint<BabyJubJubParams>>> = OnceLock::new();
+    return TABLE.get_or_init(|| fixed_base(&BABY_JUBJUB_GX, &BABY_JUBJUB_GY));
 }
 
+/// Table for B, built on first use.
+fn base_point_table() -> &'static FixedBaseTable<ExtendedPoint<BabyJubJubParams>> {
+    static TABLE: OnceLock<FixedBaseTable<ExtendedPoint<BabyJubJubParams>>> = OnceLock::new();
+    return TABLE.get_or_init(|| fixed_base(&BABY_JUBJUB_BX, &BABY_JUBJUB_BY));
+}
+
+/// a = 168700, d = 168696, with G as the generator
+#[derive(Debug, Clone, Copy)]
+pub struct BabyJubJubParams;
+
+impl TwistedEdwardsParams for BabyJubJubParams {
+    type Fe = ff::BabyJubJubFieldEle;
+    const A: &'static [u32] = &[BABY_JUBJUB_A];
+    const D: &'static [u32] = &[BABY_JUBJUB_B];
+    const GENERATOR_X: &'static [u32] = &BABY_JUBJUB_GX;
+    const GENERATOR_Y: &'static [u32] = &BABY_JUBJUB_GY;
+    const BASE_X: &'static [u32] = &BABY_JUBJUB_BX;
+    const BASE_Y: &'static [u32] = &BABY_JUBJUB_BY;
+    const ORDER: &'static [u32] = &BABY_JUBJUB_L;
+    const COFACTOR: u32 = BABY_JUBJUB_COFACTOR;
+}
+
+pub type BabyJubJubCurve = TwistedEdwardsCurve<BabyJubJubParams>;
+
 pub type BabyJubJubCurveGroup = Box<dyn ecg::CurvePoint<ff::BabyJubJubFieldEle, BabyJubJubCurve>>;
 
-impl ecg::FromBigUint<ff::BabyJubJubFieldEle, BabyJubJubCurve> for BabyJubJubCurve {
-    fn from(x: ff::BabyJubJubField, y: ff::BabyJubJubField) -> BabyJubJubCurveGroup {
-        return box BabyJubJubCurve { x: x, y: y }
-            as Box<dyn ecg::CurvePoint<ff::BabyJubJubFieldEle, BabyJubJubCurve>>;
+impl BabyJubJubCurve {
+    /// k·G through a precomputed table, k is reduced modulo h·l.
+    /// Variable time, the table lookups and additions depend on the digits
+    /// of k: only for public scalars, use `mul_ct` for secret ones.
+    pub fn mul_generator(k: &BigUint) -> BabyJubJubCurveGroup {
+        return edwards::arithmetic::<BabyJubJubParams>()
+            .mul_fixed_base(generator_table(), &(k % (Self::order() * Self::cofactor())))
+            .to_affine();
+    }
+
+    /// k·B through a precomputed table, k is reduced modulo l.
+    /// Variable time like `mul_generator`, only for public scalars.
+    pub fn mul_base_point(k: &BigUint) -> BabyJubJubCurveGroup {
+        return edwards::arithmetic::<BabyJubJubParams>()
+            .mul_fixed_base(base_point_table(), &(k % Self::order()))
+            .to_affine();
+    }
+
+    /// x is "negative" when it lies in the upper half of the field, x > (p - 1) / 2.
+    fn is_negative(x: &ff::BabyJubJubField) -> bool {
+        return x.value() > (x.prime() - 1u32) >> 1;
+    }
+
+    /// 32 byte compressed encoding compatible with circomlib's `packPoint`:
+    /// y in little endian with the sign of x in the top bit.
+    pub fn compress(p: &BabyJubJubCurveGroup) -> [u8; 32] {
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(&p.y().to_bytes_le());
+        if Self::is_negative(&p.x()) {
+            buf[31] |= 0x80;
+        }
+        return buf;
+    }
+
+    /// Inverse of `compress`, compatible with circomlib's `unpackPoint`.
+    /// Solves a·x^2 + y^2 = 1 + d·x^2·y^2 for x = ±sqrt((1 - y^2) / (a - d·y^2)).
+    pub fn decompress(buf: &[u8; 32]) -> Result<BabyJubJubCurveGroup, Error> {
+        let mut y = *buf;
+        let sign = y[31] & 0x80 != 0;
+        y[31] &= 0x7f;
+        let y = ff::BabyJubJubField::from_bytes_le(&y)?;
+
+        let y2 = y.clone() * y.clone();
+        let num = ff::BabyJubJubField::one() - y2.clone();
+        let den = ff::BabyJubJubField::from(BABY_JUBJUB_A)
+            - ff::BabyJubJubField::from(BABY_JUBJUB_B) * y2;
+        let x2 = num.checked_div(&den).map_err(|_| Error::PointNotOnCurve)?;
+        let mut x = x2.sqrt().ok_or(Error::PointNotOnCurve)?;
+        if Self::is_negative(&x) {
+            x = -x;
+        }
+        if sign {
+            if x.is_zero() {
+                return Err(Error::NonCanonicalEncoding);
+            }
+            x = -x;
+        }
+        return Ok(BabyJubJubCurve::new(x, y));
     }
 }
 
-impl ecg::Op<ff::BabyJubJubFieldEle, BabyJubJubCurve> for BabyJubJubCurve {
-    fn op(a: BabyJubJubCurveGroup, b: BabyJubJubCurveGroup) -> BabyJubJubCurveGroup {
-        if a == BabyJubJubCurveGroup::zero() {
-            return b;
+/// A point of the prime order subgroup, the only way to build one is through
+/// a subgroup check or by clearing the cofactor, so mixed order points can not
+/// reach code that takes a `SubgroupPoint`.
+#[derive(Debug, Clone)]
+pub struct SubgroupPoint(BabyJubJubCurveGroup);
+
+impl SubgroupPoint {
+    /// h·P, lands in the prime order subgroup for any P on the curve.
+    pub fn from_cofactor_cleared(p: &BabyJubJubCurveGroup) -> Result<Self, Error> {
+        if !p.is_on_curve() {
+            return Err(Error::PointNotOnCurve);
         }
-        if b == BabyJubJubCurveGroup::zero() {
-            return a;
+        return Ok(SubgroupPoint(BabyJubJubCurve::clear_cofactor(p)));
+    }
+
+    pub fn inner(&self) -> &BabyJubJubCurveGroup {
+        return &self.0;
+    }
+
+    pub fn into_inner(self) -> BabyJubJubCurveGroup {
+        return self.0;
+    }
+}
+
+impl TryFrom<BabyJubJubCurveGroup> for SubgroupPoint {
+    type Error = Error;
+    fn try_from(p: BabyJubJubCurveGroup) -> Result<Self, Self::Error> {
+        if !p.is_on_curve() {
+            return Err(Error::PointNotOnCurve);
         }
-        let m = ff::BabyJubJubField::from(BABY_JUBJUB_B) * a.x() * b.x() * a.y() * b.y();
-        let x3 = (a.x() * b.y() + a.y() * b.x()) / (ff::BabyJubJubField::one() + m.clone());
-        let y3 = (a.y() * b.y() - ff::BabyJubJubField::from(BABY_JUBJUB_A) * a.x() * b.x())
-            / (ff::BabyJubJubField::one() - m);
-        return box BabyJubJubCurve { x: x3, y: y3 };
+        if !BabyJubJubCurve::is_in_prime_subgroup(&p) {
+            return Err(Error::NotInSubgroup);
+        }
+        return Ok(SubgroupPoint(p));
     }
 }
 
-impl ecg::CurvePoint<ff::BabyJubJubFieldEle, BabyJubJubCurve> for BabyJubJubCurve {
-    fn x(&self) -> ff::BabyJubJubField {
-        return self.x.clone();
+impl From<SubgroupPoint> for BabyJubJubCurveGroup {
+    fn from(p: SubgroupPoint) -> Self {
+        return p.0;
     }
-    fn y(&self) -> ff::BabyJubJubField {
-        return self.y.clone();
+}
+
+impl PartialEq for SubgroupPoint {
+    fn eq(&self, rhs: &Self) -> bool {
+        return self.0.eq(&rhs.0);
+    }
+}
+
+impl Eq for SubgroupPoint {}
+
+impl Add for SubgroupPoint {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        return SubgroupPoint(self.0 + rhs.0);
+    }
+}
+
+impl Neg for SubgroupPoint {
+    type Output = Self;
+    fn neg(self) -> Self {
+        return SubgroupPoint(-self.0);
+    }
+}
+
+impl Sub for SubgroupPoint {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        return SubgroupPoint(self.0 - rhs.0);
+    }
+}
+
+impl Zero for SubgroupPoint {
+    fn zero() -> Self {
+        return SubgroupPoint(BabyJubJubCurveGroup::zero());
+    }
+    fn is_zero(&self) -> bool {
+        return self.0.is_zero();
+    }
+}
+
+impl Mul<BigUint> for SubgroupPoint {
+    type Output = Self;
+    fn mul(self, u: BigUint) -> Self {
+        return SubgroupPoint(self.0 * u);
+    }
+}
+
+/// k·P with k taken modulo l, which is exact on the prime order subgroup.
+/// Runs `BabyJubJubCurve::mul_ct`, so k may be a private key or a nonce.
+/// Points with a small order component have no such impl, they need the full
+/// `BigUint` scalar or a subgroup point first:
+/// ```
+/// use klefki::curves::baby_jubjub::{curve, scalar};
+/// let g = curve::BabyJubJubCurve::generator();
+/// let _ = curve::SubgroupPoint::from_cofactor_cleared(&g).unwrap() * scalar::BabyJubJubScalarField::from(5u32);
+/// ```
+/// ```compile_fail
+/// use klefki::curves::baby_jubjub::{curve, scalar};
+/// let g = curve::BabyJubJubCurve::generator();
+/// let _ = g * scalar::BabyJubJubScalarField::from(5u32);
+/// ```
+impl Mul<scalar::BabyJubJubScalarField> for SubgroupPoint {
+    type Output = Self;
+    fn mul(self, k: scalar::BabyJubJubScalarField) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&k.element().to_bytes_le());
+        return SubgroupPoint(BabyJubJubCurve::mul_ct(&self.0, &bytes));
     }
 }
 
 #[cfg(test)]
 mod tests {
+    extern crate test;
+
+    use crate::algebra::groups::arithmetic::{double_and_add_algorithm, wnaf_algorithm, ScalarMulMethod};
+    use crate::algebra::groups::ecg;
     use crate::algebra::traits::Scalar;
-    use crate::curves::baby_jubjub::curve;
+    use crate::curves::baby_jubjub::{curve, ff, scalar};
+    use crate::Error;
+    use num::Zero;
     use num_bigint::BigUint;
     use std::convert::TryFrom;
+    use test::Bencher;
 
     #[test]
     fn test_addition() {
@@ -89,12 +342,35 @@ mod tests {
         let y2 = 1u32;
         let x3 = 0u32;
         let y3 = 1u32;
-        let g1 = curve::BabyJubJubCurveGroup::from((x1, y1));
-        let g2 = curve::BabyJubJubCurveGroup::from((x2, y2));
-        let g3 = curve::BabyJubJubCurveGroup::from((x3, y3));
+        let g1 = curve::BabyJubJubCurveGroup::try_from((x1, y1)).unwrap();
+        let g2 = curve::BabyJubJubCurveGroup::try_from((x2, y2)).unwrap();
+        let g3 = curve::BabyJubJubCurveGroup::try_from((x3, y3)).unwrap();
         assert_eq!(g1 + g2 == g3, true);
     }
 
+    #[test]
+    fn test_identity_and_negation() {
+        let b = curve::BabyJubJubCurve::base_point();
+        let o = curve::BabyJubJubCurveGroup::zero();
+        assert_eq!(o == curve::BabyJubJubCurveGroup::try_from((0u32, 1u32)).unwrap(), true);
+        assert_eq!(
+            ecg::from_biguint_unchecked::<ff::BabyJubJubFieldEle, curve::BabyJubJubCurve>(
+                BigUint::from(0u32),
+                BigUint::from(0u32)
+            )
+            .is_zero(),
+            false
+        );
+        assert_eq!(o.clone() + b.clone() == b.clone(), true);
+        assert_eq!(b.clone() + o.clone() == b.clone(), true);
+        assert_eq!((-o.clone()).is_zero(), true);
+
+        let nb = -b.clone();
+        assert_eq!(nb.x() == -b.x() && nb.y() == b.y(), true);
+        assert_eq!((b.clone() + nb).is_zero(), true);
+        assert_eq!((b.clone() - b).is_zero(), true);
+    }
+
     #[test]
     fn test_base_point() {
         let bx = "5299619240641551281634865583518297030282874472190772894086521144482721001553";
@@ -114,7 +390,7 @@ mod tests {
         let by = "16950150798460657717958625567821834550301663161624707787222815936182638968203";
         let l = "2736030358979909402780800718157159386076813972158567259200215660948447373041";
 
-        let p = curve::BabyJubJubCurveGroup::from((x, y));
+        let p = curve::BabyJubJubCurveGroup::try_from((x, y)).unwrap();
         let b = curve::BabyJubJubCurveGroup::try_from((bx, by)).unwrap();
 
         assert_eq!(
@@ -128,4 +404,294 @@ mod tests {
             true
         );
     }
+
+    #[test]
+    fn test_compress() {
+        let x = "17777552123799933955779906779655732241715742912184938656739573121738514868268";
+        let y = "2626589144620713026669568689430873010625803728049924121243784502389097019475";
+        let packed = "53b81ed5bffe9545b54016234682e7b2f699bd42a5e9eae27ff4051bc698ce85";
+        let p = curve::BabyJubJubCurveGroup::try_from((x, y)).unwrap();
+        let buf = curve::BabyJubJubCurve::compress(&p);
+        let hex: String = buf.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex, packed);
+        assert_eq!(curve::BabyJubJubCurve::decompress(&buf).unwrap() == p, true);
+    }
+
+    #[test]
+    fn test_compress_roundtrip() {
+        let b = curve::BabyJubJubCurve::base_point();
+        for k in 1usize..8 {
+            let p = b.clone().scalar(k);
+            let buf = curve::BabyJubJubCurve::compress(&p);
+            assert_eq!(curve::BabyJubJubCurve::decompress(&buf).unwrap() == p.clone(), true);
+            let q = -p;
+            let buf = curve::BabyJubJubCurve::compress(&q);
+            assert_eq!(curve::BabyJubJubCurve::decompress(&buf).unwrap() == q, true);
+        }
+        let o = curve::BabyJubJubCurveGroup::try_from((0u32, 1u32)).unwrap();
+        let buf = curve::BabyJubJubCurve::compress(&o);
+        assert_eq!(curve::BabyJubJubCurve::decompress(&buf).unwrap() == o, true);
+    }
+
+    #[test]
+    fn test_decompress_rejects_invalid() {
+        // y >= p
+        let mut buf = [0xffu8; 32];
+        buf[31] = 0x7f;
+        assert_eq!(
+            curve::BabyJubJubCurve::decompress(&buf).unwrap_err(),
+            Error::NonCanonicalEncoding
+        );
+        // y = 2 has no x on the curve
+        let mut buf = [0u8; 32];
+        buf[0] = 2;
+        assert_eq!(
+            curve::BabyJubJubCurve::decompress(&buf).unwrap_err(),
+            Error::PointNotOnCurve
+        );
+        // (0, 1) with the sign bit set
+        let mut buf = [0u8; 32];
+        buf[0] = 1;
+        buf[31] = 0x80;
+        assert_eq!(
+            curve::BabyJubJubCurve::decompress(&buf).unwrap_err(),
+            Error::NonCanonicalEncoding
+        );
+    }
+
+    #[test]
+    fn test_on_curve() {
+        let b = curve::BabyJubJubCurve::base_point();
+        assert_eq!(b.is_on_curve(), true);
+        assert_eq!((b.clone() + b.clone()).is_on_curve(), true);
+        assert_eq!(curve::BabyJubJubCurveGroup::zero().is_on_curve(), true);
+
+        assert_eq!(
+            curve::BabyJubJubCurveGroup::try_from((b.x().value(), BigUint::from(1u32))).unwrap_err(),
+            Error::PointNotOnCurve
+        );
+        assert_eq!(
+            curve::BabyJubJubCurveGroup::try_from((1u32, 1u32)).unwrap_err(),
+            Error::PointNotOnCurve
+        );
+        let off = ecg::from_biguint_unchecked::<ff::BabyJubJubFieldEle, curve::BabyJubJubCurve>(
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(off.is_on_curve(), false);
+    }
+
+    #[test]
+    fn test_prime_subgroup() {
+        let g = curve::BabyJubJubCurve::generator();
+        let b = curve::BabyJubJubCurve::base_point();
+        let o = curve::BabyJubJubCurveGroup::zero();
+        assert_eq!(curve::BabyJubJubCurve::is_in_prime_subgroup(&b), true);
+        assert_eq!(curve::BabyJubJubCurve::is_in_prime_subgroup(&o), true);
+        assert_eq!(curve::BabyJubJubCurve::is_in_prime_subgroup(&g), false);
+        assert_eq!(curve::BabyJubJubCurve::clear_cofactor(&g) == b, true);
+        assert_eq!(
+            curve::BabyJubJubCurve::is_in_prime_subgroup(&curve::BabyJubJubCurve::clear_cofactor(&g)),
+            true
+        );
+    }
+
+    #[test]
+    fn test_small_order() {
+        let b = curve::BabyJubJubCurve::base_point();
+        let o = curve::BabyJubJubCurveGroup::zero();
+        // (0, -1) has order 2
+        let minus_one = (-ff::BabyJubJubField::from(1u32)).value();
+        let t = curve::BabyJubJubCurveGroup::try_from((BigUint::from(0u32), minus_one)).unwrap();
+        assert_eq!((t.clone() + t.clone()).is_zero(), true);
+        assert_eq!(curve::BabyJubJubCurve::is_small_order(&o), true);
+        assert_eq!(curve::BabyJubJubCurve::is_small_order(&t), true);
+        assert_eq!(curve::BabyJubJubCurve::is_small_order(&b), false);
+        assert_eq!(curve::BabyJubJubCurve::is_in_prime_subgroup(&t), false);
+        assert_eq!(curve::BabyJubJubCurve::is_in_prime_subgroup(&(b + t)), false);
+    }
+
+    #[test]
+    fn test_subgroup_point() {
+        let g = curve::BabyJubJubCurve::generator();
+        let b = curve::BabyJubJubCurve::base_point();
+        assert_eq!(curve::SubgroupPoint::try_from(g.clone()).unwrap_err(), Error::NotInSubgroup);
+
+        let sb = curve::SubgroupPoint::try_from(b.clone()).unwrap();
+        assert_eq!(curve::SubgroupPoint::from_cofactor_cleared(&g).unwrap() == sb.clone(), true);
+        let off = ecg::from_biguint_unchecked::<ff::BabyJubJubFieldEle, curve::BabyJubJubCurve>(
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+        );
+        assert_eq!(curve::SubgroupPoint::from_cofactor_cleared(&off).unwrap_err(), Error::PointNotOnCurve);
+        assert_eq!(sb.inner().eq(&b), true);
+        let sum = sb.clone() + sb.clone();
+        assert_eq!(sum.clone().into_inner() == b.clone().scalar(2usize), true);
+        assert_eq!((sum - sb.clone() - sb.clone()).is_zero(), true);
+        let l = BigUint::from_slice(&curve::BABY_JUBJUB_L);
+        assert_eq!((sb * l).is_zero(), true);
+    }
+
+    #[test]
+    fn test_wnaf_matches_double_and_add() {
+        let b = curve::BabyJubJubCurve::base_point();
+        let k = BigUint::from_slice(&curve::BABY_JUBJUB_L) - 12345u32;
+        let expected = double_and_add_algorithm(k.clone(), b.clone(), curve::BabyJubJubCurveGroup::zero());
+        assert_eq!(wnaf_algorithm(k.clone(), b.clone(), curve::BabyJubJubCurveGroup::zero(), 4) == expected.clone(), true);
+        assert_eq!(b.scalar(k) == expected, true);
+    }
+
+    #[test]
+    fn test_mul_scalar_field() {
+        // G has a small order component, only its cofactor cleared multiple
+        // takes scalars mod l
+        let g = curve::BabyJubJubCurve::generator();
+        let sg = curve::SubgroupPoint::from_cofactor_cleared(&g).unwrap();
+        let l = BigUint::from_slice(&curve::BABY_JUBJUB_L);
+        let a = l.clone() - 12345u32;
+        let c = BigUint::parse_bytes(b"1a2b3c4d5e6f708192a3b4c5d6e7f8", 16).unwrap();
+        let (sa, sc) = (scalar::BabyJubJubScalarField::from(a.clone()), scalar::BabyJubJubScalarField::from(c));
+        assert_eq!(sg.clone() * sa.clone() == curve::SubgroupPoint::from_cofactor_cleared(&g.scalar(a)).unwrap(), true);
+        assert_eq!(sg.clone() * (sa.clone() + sc.clone()) == sg.clone() * sa.clone() + sg.clone() * sc.clone(), true);
+        assert_eq!(sg.clone() * (sa.clone() * sc.clone()) == (sg.clone() * sa) * sc, true);
+        assert_eq!((sg * scalar::BabyJubJubScalarField::from(l)).is_zero(), true);
+    }
+
+    fn bench_scalar() -> BigUint {
+        return BigUint::from_slice(&curve::BABY_JUBJUB_L) - 1u32;
+    }
+
+    #[test]
+    fn test_eip2494_constants() {
+        let gx = "995203441582195749578291179787384436505546430278305826713579947235728471134";
+        let gy = "5472060717959818805561601436314318772137091100104008585924551046643952123905";
+        let bx = "5299619240641551281634865583518297030282874472190772894086521144482721001553";
+        let by = "16950150798460657717958625567821834550301663161624707787222815936182638968203";
+        let l = "2736030358979909402780800718157159386076813972158567259200215660948447373041";
+        let g = curve::BabyJubJubCurve::generator();
+        let b = curve::BabyJubJubCurve::base_point();
+        assert_eq!(g.x().value().to_string(), gx);
+        assert_eq!(g.y().value().to_string(), gy);
+        assert_eq!(b.x().value().to_string(), bx);
+        assert_eq!(b.y().value().to_string(), by);
+        assert_eq!(curve::BabyJubJubCurve::order().to_string(), l);
+        assert_eq!(curve::BabyJubJubCurve::cofactor(), BigUint::from(8u32));
+    }
+
+    #[test]
+    fn test_mul_generator() {
+        let g = curve::BabyJubJubCurve::generator();
+        let b = curve::BabyJubJubCurve::base_point();
+        let l = BigUint::from_slice(&curve::BABY_JUBJUB_L);
+        assert_eq!(curve::BabyJubJubCurve::mul_generator(&BigUint::from(1u32)) == g.clone(), true);
+        assert_eq!(curve::BabyJubJubCurve::mul_generator(&BigUint::from(8u32)) == b.clone(), true);
+        assert_eq!(curve::BabyJubJubCurve::mul_base_point(&BigUint::from(1u32)) == b.clone(), true);
+        assert_eq!(curve::BabyJubJubCurve::mul_base_point(&l).is_zero(), true);
+
+        let k = l.clone() * 3u32 - 12345u32;
+        assert_eq!(curve::BabyJubJubCurve::mul_generator(&k) == g.scalar(k.clone()), true);
+        assert_eq!(curve::BabyJubJubCurve::mul_base_point(&k) == b.clone().scalar(k.clone()), true);
+        // wider than the table, reduced first
+        let wide = k.clone() << 300;
+        assert_eq!(curve::BabyJubJubCurve::mul_base_point(&wide) == b.scalar(wide), true);
+    }
+
+    #[test]
+    fn test_msm() {
+        let g = curve::BabyJubJubCurve::generator();
+        let l = BigUint::from_slice(&curve::BABY_JUBJUB_L);
+        let mut points = Vec::<curve::BabyJubJubCurveGroup>::new();
+        let mut scalars = Vec::<BigUint>::new();
+        let mut expected = curve::BabyJubJubCurveGroup::zero();
+        let mut p = g.clone();
+        for i in 0u32..80 {
+            let k = (l.clone() - BigUint::from(i) * 977u32) >> (i as usize % 5);
+            expected = expected + p.clone().scalar(k.clone());
+            points.push(p.clone());
+            scalars.push(k);
+            p = p + g.clone();
+        }
+        for n in [0usize, 1, 3, 80].iter() {
+            let e = points[..*n]
+                .iter()
+                .zip(scalars[..*n].iter())
+                .fold(curve::BabyJubJubCurveGroup::zero(), |acc, (p, k)| acc + p.clone().scalar(k.clone()));
+            assert_eq!(ecg::msm(&points[..*n], &scalars[..*n]) == e, true);
+        }
+        assert_eq!(ecg::msm(&points, &scalars) == expected, true);
+    }
+
+    #[bench]
+    fn bench_msm_64(bench: &mut Bencher) {
+        let b = curve::BabyJubJubCurve::base_point();
+        let points = (1usize..65).map(|i| b.clone().scalar(i)).collect::<Vec<curve::BabyJubJubCurveGroup>>();
+        let scalars = (1u32..65).map(|i| bench_scalar() - i).collect::<Vec<BigUint>>();
+        bench.iter(|| ecg::msm(&points, &scalars));
+    }
+
+    #[bench]
+    fn bench_separate_64(bench: &mut Bencher) {
+        let b = curve::BabyJubJubCurve::base_point();
+        let points = (1usize..65).map(|i| b.clone().scalar(i)).collect::<Vec<curve::BabyJubJubCurveGroup>>();
+        let scalars = (1u32..65).map(|i| bench_scalar() - i).collect::<Vec<BigUint>>();
+        bench.iter(|| {
+            points
+                .iter()
+                .zip(scalars.iter())
+                .fold(curve::BabyJubJubCurveGroup::zero(), |acc, (p, k)| acc + p.clone().scalar(k.clone()))
+        });
+    }
+
+    #[bench]
+    fn bench_mul_base_point(bench: &mut Bencher) {
+        let k = bench_scalar();
+        curve::BabyJubJubCurve::mul_base_point(&k);
+        bench.iter(|| curve::BabyJubJubCurve::mul_base_point(&k));
+    }
+
+    /// Against `bench_generator_scalar`
+    #[bench]
+    fn bench_mul_generator(bench: &mut Bencher) {
+        let k = bench_scalar();
+        curve::BabyJubJubCurve::mul_generator(&k);
+        bench.iter(|| curve::BabyJubJubCurve::mul_generator(&k));
+    }
+
+    #[bench]
+    fn bench_generator_scalar(bench: &mut Bencher) {
+        let g = curve::BabyJubJubCurve::generator();
+        let k = bench_scalar();
+        bench.iter(|| g.clone().scalar(k.clone()));
+    }
+
+    #[bench]
+    fn bench_double_and_add(bench: &mut Bencher) {
+        let b = curve::BabyJubJubCurve::base_point();
+        let k = bench_scalar();
+        bench.iter(|| ecg::scalar_mul_with(ScalarMulMethod::DoubleAndAdd, b.clone(), k.clone()));
+    }
+
+    #[bench]
+    fn bench_wnaf(bench: &mut Bencher) {
+        let b = curve::BabyJubJubCurve::base_point();
+        let k = bench_scalar();
+        bench.iter(|| ecg::scalar_mul_with(ScalarMulMethod::Wnaf(4), b.clone(), k.clone()));
+    }
+
+    #[bench]
+    fn bench_scalar_mul(bench: &mut Bencher) {
+        let b = curve::BabyJubJubCurve::base_point();
+        let k = bench_scalar();
+        bench.iter(|| b.clone().scalar(k.clone()));
+    }
+
+    #[test]
+    fn test_mul_ct() {
+        let b = curve::BabyJubJubCurve::base_point();
+        let k = BigUint::parse_bytes(b"1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f80", 16).unwrap();
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(&k.to_bytes_le());
+        assert_eq!(curve::BabyJubJubCurve::mul_ct(&b, &buf) == b.clone().scalar(k), true);
+        assert_eq!(curve::BabyJubJubCurve::mul_ct(&b, &[0u8; 32]).is_zero(), true);
+    }
 }
@@ -1,35 +1,89 @@
-use crate::algebra::fields::prime;
-use num_bigint::BigUint;
+use klefki_derive::prime_field;
 
-const BABY_JUBJUB_P: [u32; 8] = [
-    0xf0000001u32,
-    0x43e1f593u32,
-    0x79b97091u32,
-    0x2833e848u32,
-    0x8181585du32,
-    0xb85045b6u32,
-    0xe131a029u32,
-    0x30644e72u32,
-];
+prime_field!(
+    pub BabyJubJubField = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    generator = 5,
+    factors = [237073, 405928799, 1670836401704629, 13818364434197438864469338081],
+);
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-pub struct BabyJubJubFieldEle {
-    pub value: BigUint,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algebra::traits::{ByteEncoding, Sqrt};
+    use crate::Error;
+    use num_bigint::BigUint;
+    use rand::Rng;
 
-impl prime::Property<BabyJubJubFieldEle> for BabyJubJubFieldEle {
-    fn prime(&self) -> BigUint {
-        return BigUint::from_slice(&BABY_JUBJUB_P);
+    #[test]
+    fn ff_sqrt() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..16 {
+            let x = BabyJubJubField::from(BigUint::from_slice(&rng.gen::<[u32; 8]>()));
+            let sq = x.clone() * x.clone();
+            assert_eq!(sq.is_square(), true);
+            let r = sq.sqrt().unwrap();
+            assert_eq!(r.clone() * r.clone() == sq, true);
+            assert_eq!(r == x.clone() || r == -x, true);
+        }
     }
-    fn value(&self) -> BigUint {
-        return self.value.clone();
+
+    #[test]
+    fn ff_non_residue() {
+        let g = BabyJubJubField::from(5u32);
+        assert_eq!(g.legendre(), -1);
+        assert_eq!(g.sqrt().is_none(), true);
+        assert_eq!(BabyJubJubField::from(0u32).legendre(), 0);
+        assert_eq!(BabyJubJubField::from(4u32).legendre(), 1);
     }
-}
 
-impl prime::FromBigUint for BabyJubJubFieldEle {
-    fn from(value: BigUint) -> prime::BoxedPrimeField<BabyJubJubFieldEle> {
-        return box Self { value: value };
+    #[test]
+    fn ff_bytes() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..16 {
+            let x = BabyJubJubField::from(BigUint::from_slice(&rng.gen::<[u32; 8]>()));
+            let le = x.to_bytes_le();
+            let be = x.to_bytes_be();
+            assert_eq!(le.len(), 32);
+            assert_eq!(be.len(), 32);
+            assert_eq!(BabyJubJubField::from_bytes_le(&le).unwrap() == x.clone(), true);
+            assert_eq!(BabyJubJubField::from_bytes_be(&be).unwrap() == x.clone(), true);
+            assert_eq!(BabyJubJubField::from_hex(&x.to_hex()).unwrap() == x, true);
+        }
+
+        let one = BabyJubJubField::from(1u32);
+        let mut le = vec![0u8; 32];
+        le[0] = 1;
+        assert_eq!(one.to_bytes_le(), le);
+        assert_eq!(
+            one.to_hex(),
+            "0000000000000000000000000000000000000000000000000000000000000001"
+        );
     }
-}
 
-pub type BabyJubJubField = Box<dyn prime::Property<BabyJubJubFieldEle>>;
+    #[test]
+    fn ff_bytes_strict() {
+        let p = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        let p_minus_one = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+        assert_eq!(
+            BabyJubJubField::from_hex(p).unwrap_err(),
+            Error::NonCanonicalEncoding
+        );
+        assert_eq!(
+            BabyJubJubField::from_hex(p_minus_one).unwrap() == -BabyJubJubField::from(1u32),
+            true
+        );
+        assert_eq!(
+            BabyJubJubField::from_hex(&format!("0{}", p)).unwrap_err(),
+            Error::InvalidHex
+        );
+        assert_eq!(BabyJubJubField::from_hex("zz").unwrap_err(), Error::InvalidHex);
+        assert_eq!(
+            BabyJubJubField::from_bytes_le(&[0u8; 31]).unwrap_err(),
+            Error::InvalidLength {
+                expected: 32,
+                actual: 31
+            }
+        );
+        assert_eq!(BabyJubJubField::from_bytes_be(&[0xffu8; 32]).is_err(), true);
+    }
+}
@@ -1,2 +1,3 @@
 pub mod curve;
 pub mod ff;
+pub mod scalar;
@@ -0,0 +1,25 @@
+use klefki_derive::prime_field;
+
+// Integers modulo the order l of the prime order subgroup, for private keys,
+// nonces and other exponents. `SubgroupPoint * k` runs `mul_ct` on
+// `to_bytes_le`.
+prime_field!(
+    pub BabyJubJubScalarField = "2736030358979909402780800718157159386076813972158567259200215660948447373041",
+    generator = 31,
+    factors = [32151195060611136810608359, 178259130663561045147472537592047227885001],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::baby_jubjub::curve;
+    use num::Zero;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_modulus() {
+        let l = BigUint::from_slice(&curve::BABY_JUBJUB_L);
+        assert_eq!(BabyJubJubScalarField::from(0u32).prime(), l.clone());
+        assert_eq!(BabyJubJubScalarField::from(l).is_zero(), true);
+    }
+}
@@ -0,0 +1,541 @@
+/// DOC: https://www.secg.org/sec2-v2.pdf
+use crate::algebra::groups::arithmetic::FixedBaseTable;
+use crate::algebra::groups::ecg;
+use crate::algebra::groups::jacobian::{jacobian_fixed_base_table, jacobian_mul_fixed_base};
+use crate::algebra::groups::projective::{complete_add, complete_double, ProjectivePoint};
+use crate::algebra::groups::weierstrass::{ShortWeierstrassCurve, ShortWeierstrassParams};
+use crate::algebra::traits::{ByteEncoding, ConditionalSwap, Sqrt};
+use crate::curves::secp256k1::{ff, glv, scalar};
+use crate::Error;
+use num::Zero;
+use num_bigint::BigUint;
+use std::ops::Mul;
+use std::sync::OnceLock;
+
+pub const SECP256K1_A: u32 = 0u32;
+pub const SECP256K1_B: u32 = 7u32;
+
+/// The curve order is h·n
+pub const SECP256K1_COFACTOR: u32 = 1u32;
+
+/// Order n of the group generated by G
+pub const SECP256K1_N: [u32; 8] = [
+    0xd0364141u32,
+    0xbfd25e8cu32,
+    0xaf48a03bu32,
+    0xbaaedce6u32,
+    0xfffffffeu32,
+    0xffffffffu32,
+    0xffffffffu32,
+    0xffffffffu32,
+];
+
+/// x coordinate of the standard generator G
+pub const SECP256K1_GX: [u32; 8] = [
+    0x16f81798u32,
+    0x59f2815bu32,
+    0x2dce28d9u32,
+    0x029bfcdbu32,
+    0xce870b07u32,
+    0x55a06295u32,
+    0xf9dcbbacu32,
+    0x79be667eu32,
+];
+
+/// y coordinate of the standard generator G
+pub const SECP256K1_GY: [u32; 8] = [
+    0xfb10d4b8u32,
+    0x9c47d08fu32,
+    0xa6855419u32,
+    0xfd17b448u32,
+    0x0e1108a8u32,
+    0x5da4fbfcu32,
+    0x26a3c465u32,
+    0x483ada77u32,
+];
+
+/// y^2 = x^3 + 7 with G as the generator. `Scalar` and `Mul` go through
+/// the GLV endomorphism, see `glv::mul`.
+#[derive(Debug, Clone, Copy)]
+pub struct Secp256k1Params;
+
+impl ShortWeierstrassParams for Secp256k1Params {
+    type Fe = ff::Secp256k1FieldEle;
+    const A: &'static [u32] = &[SECP256K1_A];
+    const B: &'static [u32] = &[SECP256K1_B];
+    const GENERATOR_X: &'static [u32] = &SECP256K1_GX;
+    const GENERATOR_Y: &'static [u32] = &SECP256K1_GY;
+    const ORDER: &'static [u32] = &SECP256K1_N;
+    const COFACTOR: u32 = SECP256K1_COFACTOR;
+
+    /// GLV split into two half length scalars in Jacobian coordinates,
+    /// normalized once at the end.
+    fn scalar_mul(p: &Secp256k1CurveGroup, k: &BigUint) -> Secp256k1CurveGroup {
+        return glv::mul(p, k);
+    }
+}
+
+pub type Secp256k1Curve = ShortWeierstrassCurve<Secp256k1Params>;
+
+pub type Secp256k1CurveGroup = Box<dyn ecg::CurvePoint<ff::Secp256k1FieldEle, Secp256k1Curve>>;
+
+/// SEC1 point encoding prefixes
+pub const SEC1_INFINITY: u8 = 0x00;
+pub const SEC1_EVEN: u8 = 0x02;
+pub const SEC1_ODD: u8 = 0x03;
+pub const SEC1_UNCOMPRESSED: u8 = 0x04;
+
+/// The table for G covers scalars of this many bits in windows of
+/// `FIXED_BASE_WINDOW` bits.
+const FIXED_BASE_BITS: usize = 256;
+const FIXED_BASE_WINDOW: usize = 6;
+
+/// Table for G, built on first use.
+fn generator_table() -> &'static FixedBaseTable<Option<(ff::Secp256k1FieldEle, ff::Secp256k1FieldEle)>> {
+    static TABLE: OnceLock<FixedBaseTable<Option<(ff::Secp256k1FieldEle, ff::Secp256k1FieldEle)>>> = OnceLock::new();
+    return TABLE.get_or_init(|| {
+        let g = (
+            ff::Secp256k1FieldEle::from(BigUint::from_slice(&SECP256K1_GX)),
+            ff::Secp256k1FieldEle::from(BigUint::from_slice(&SECP256K1_GY)),
+        );
+        return jacobian_fixed_base_table(
+            &g,
+            &Secp256k1Curve::a(),
+            FIXED_BASE_BITS,
+            FIXED_BASE_WINDOW,
+        );
+    });
+}
+
+impl Secp256k1Curve {
+    /// SEC 2 calls G the base point, the cofactor is 1 so it already
+    /// generates the whole group.
+    pub fn base_point() -> Secp256k1CurveGroup {
+        return Self::generator();
+    }
+
+    /// k·G through a precomputed table, k is reduced modulo n.
+    /// Variable time, the table lookups and additions depend on the digits
+    /// of k: only for public scalars, use `mul_ct` for secret ones.
+    pub fn mul_generator(k: &BigUint) -> Secp256k1CurveGroup {
+        let k = k % Self::order();
+        return Self::from_jacobian(&jacobian_mul_fixed_base(generator_table(), &k, &Self::a()));
+    }
+
+    /// x^3 + 7
+    fn rhs(x: &ff::Secp256k1Field) -> ff::Secp256k1Field {
+        return x.clone() * x.clone() * x.clone() + ff::Secp256k1Field::from(SECP256K1_B);
+    }
+
+    fn is_odd(y: &ff::Secp256k1Field) -> bool {
+        return y.to_bytes_le()[0] & 1 == 1;
+    }
+
+    /// k·P for a secret scalar k, e.g. a private key, given as 32 little
+    /// endian bytes like `ByteEncoding::to_bytes_le` of a scalar field element,
+    /// the same as `BabyJubJubCurve::mul_ct`. A Montgomery ladder over all 256
+    /// bits with conditional swaps and the complete projective formulas, so
+    /// the sequence of field operations does not depend on k; prefer this over
+    /// `Scalar`/`Mul`, which branch on the bits of k.
+    pub fn mul_ct(p: &Secp256k1CurveGroup, k: &[u8; 32]) -> Secp256k1CurveGroup {
+        let b3 = ff::Secp256k1FieldEle::from(3 * SECP256K1_B);
+        let p = if p.is_infinity() {
+            ProjectivePoint::identity()
+        } else {
+            ProjectivePoint::from_affine(Some((
+                p.x().element(),
+                p.y().element(),
+            )))
+        };
+        let mut r0 = ProjectivePoint::identity();
+        let mut r1 = p;
+        let mut swap = 0u8;
+        for i in (0..256).rev() {
+            let bit = (k[i >> 3] >> (i & 7)) & 1;
+            swap ^= bit;
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, swap);
+            swap = bit;
+            r1 = complete_add(&r0, &r1, &b3);
+            r0 = complete_double(&r0, &b3);
+        }
+        ProjectivePoint::conditional_swap(&mut r0, &mut r1, swap);
+        return Self::from_affine(r0.to_affine());
+    }
+
+    /// SEC1 encoding: 33 bytes 0x02/0x03 || x when compressed, 65 bytes 0x04 || x || y
+    /// otherwise, and the single byte 0x00 for the point at infinity.
+    /// # ref:
+    /// * https://www.secg.org/sec1-v2.pdf, 2.3.3
+    pub fn to_sec1(p: &Secp256k1CurveGroup, compressed: bool) -> Vec<u8> {
+        if p.is_zero() {
+            return vec![SEC1_INFINITY];
+        }
+        let mut buf = Vec::<u8>::with_capacity(65);
+        if compressed {
+            buf.push(if Self::is_odd(&p.y()) { SEC1_ODD } else { SEC1_EVEN });
+            buf.extend(p.x().to_bytes_be());
+        } else {
+            buf.push(SEC1_UNCOMPRESSED);
+            buf.extend(p.x().to_bytes_be());
+            buf.extend(p.y().to_bytes_be());
+        }
+        return buf;
+    }
+
+    /// Inverse of `to_sec1`, recovers y from x for compressed encodings
+    /// and checks that the decoded point is on the curve.
+    /// # ref:
+    /// * https://www.secg.org/sec1-v2.pdf, 2.3.4
+    pub fn from_sec1(buf: &[u8]) -> Result<Secp256k1CurveGroup, Error> {
+        let expected = match buf.first() {
+            Some(&SEC1_INFINITY) => 1,
+            Some(&SEC1_EVEN) | Some(&SEC1_ODD) => 33,
+            Some(&SEC1_UNCOMPRESSED) => 65,
+            Some(_) => return Err(Error::NonCanonicalEncoding),
+            None => {
+                return Err(Error::InvalidLength {
+                    expected: 33,
+                    actual: 0,
+                })
+            }
+        };
+        if buf.len() != expected {
+            return Err(Error::InvalidLength {
+                expected: expected,
+                actual: buf.len(),
+            });
+        }
+        if buf[0] == SEC1_INFINITY {
+            return Ok(Secp256k1CurveGroup::zero());
+        }
+
+        let x = ff::Secp256k1Field::from_bytes_be(&buf[1..33])?;
+        let y = if buf[0] == SEC1_UNCOMPRESSED {
+            let y = ff::Secp256k1Field::from_bytes_be(&buf[33..65])?;
+            if y.clone() * y.clone() != Self::rhs(&x) {
+                return Err(Error::PointNotOnCurve);
+            }
+            y
+        } else {
+            let y = Self::rhs(&x).sqrt().ok_or(Error::PointNotOnCurve)?;
+            if Self::is_odd(&y) == (buf[0] == SEC1_ODD) {
+                y
+            } else {
+                -y
+            }
+        };
+        return Ok(Self::new(x, y));
+    }
+}
+
+/// k·P with k taken modulo n. Runs `Secp256k1Curve::mul_ct`, so k may be a
+/// private key or a nonce; `Mul<BigUint>` is the faster variable time path.
+impl Mul<scalar::Secp256k1ScalarField> for Secp256k1CurveGroup {
+    type Output = Self;
+    fn mul(self, k: scalar::Secp256k1ScalarField) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&k.element().to_bytes_le());
+        return Secp256k1Curve::mul_ct(&self, &bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+
+    use crate::algebra::groups::ecg;
+    use crate::algebra::traits::Scalar;
+    use crate::curves::secp256k1::{curve, ff, scalar};
+    use crate::Error;
+    use num::Zero;
+    use num_bigint::BigUint;
+    use std::convert::TryFrom;
+    use test::Bencher;
+
+    fn generator() -> curve::Secp256k1CurveGroup {
+        return curve::Secp256k1CurveGroup::try_from((&curve::SECP256K1_GX[..], &curve::SECP256K1_GY[..]))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_generator() {
+        let gx = "55066263022277343669578718895168534326250603453777594175500187360389116729240";
+        let gy = "32670510020758816978083085130507043184471273380659243275938904335757337482424";
+        let n = "115792089237316195423570985008687907852837564279074904382605163141518161494337";
+        let g = curve::Secp256k1CurveGroup::try_from((gx, gy)).unwrap();
+        assert_eq!(g == generator(), true);
+        assert_eq!(curve::Secp256k1Curve::generator() == g.clone(), true);
+        assert_eq!(curve::Secp256k1Curve::base_point() == g, true);
+        assert_eq!(curve::Secp256k1Curve::order().to_string(), n);
+        assert_eq!(curve::Secp256k1Curve::cofactor(), BigUint::from(1u32));
+    }
+
+    #[test]
+    fn test_double() {
+        let x2 = "89565891926547004231252920425935692360644145829622209833684329913297188986597";
+        let y2 = "12158399299693830322967808612713398636155367887041628176798871954788371653930";
+        let g2 = curve::Secp256k1CurveGroup::try_from((x2, y2)).unwrap();
+        assert_eq!(generator() + generator() == g2.clone(), true);
+        assert_eq!(generator().scalar(2) == g2, true);
+    }
+
+    #[test]
+    fn test_addition() {
+        let x2 = "89565891926547004231252920425935692360644145829622209833684329913297188986597";
+        let y2 = "12158399299693830322967808612713398636155367887041628176798871954788371653930";
+        let x3 = "112711660439710606056748659173929673102114977341539408544630613555209775888121";
+        let y3 = "25583027980570883691656905877401976406448868254816295069919888960541586679410";
+        let x5 = "21505829891763648114329055987619236494102133314575206970830385799158076338148";
+        let y5 = "98003708678762621233683240503080860129026887322874138805529884920309963580118";
+        let g2 = curve::Secp256k1CurveGroup::try_from((x2, y2)).unwrap();
+        let g3 = curve::Secp256k1CurveGroup::try_from((x3, y3)).unwrap();
+        let g5 = curve::Secp256k1CurveGroup::try_from((x5, y5)).unwrap();
+        assert_eq!(generator() + g2.clone() == g3.clone(), true);
+        assert_eq!(g2 + g3 == g5.clone(), true);
+        assert_eq!(generator().scalar(5) == g5, true);
+    }
+
+    #[test]
+    fn test_inverse() {
+        let g = generator();
+        assert_eq!((g.clone() + (-g)).is_zero(), true);
+    }
+
+    #[test]
+    fn test_point_at_infinity() {
+        let o = curve::Secp256k1CurveGroup::zero();
+        assert_eq!(o.is_infinity(), true);
+        assert_eq!(o.clone() == curve::Secp256k1CurveGroup::zero(), true);
+        assert_eq!(o.clone().clone().is_infinity(), true);
+        assert_eq!((-o.clone()).is_zero(), true);
+        assert_eq!(o.clone() + generator() == generator(), true);
+        assert_eq!(generator() + o.clone() == generator(), true);
+        assert_eq!(o.clone() + o.clone() == o.clone(), true);
+        assert_eq!(generator() == o.clone(), false);
+        assert_eq!(o.clone().scalar(5usize).is_zero(), true);
+        assert_eq!(generator().scalar(0usize).is_zero(), true);
+
+        // (0, 0) is an ordinary coordinate pair, not the identity
+        let zz = ecg::from_biguint_unchecked::<ff::Secp256k1FieldEle, curve::Secp256k1Curve>(
+            BigUint::from(0u32),
+            BigUint::from(0u32),
+        );
+        assert_eq!(zz.is_zero(), false);
+        assert_eq!(zz == o, false);
+    }
+
+    #[test]
+    fn test_generator_order() {
+        let n = BigUint::from_slice(&curve::SECP256K1_N);
+        assert_eq!(generator().scalar(n.clone()).is_zero(), true);
+        assert_eq!(
+            generator() * (n - BigUint::from(1u32)) == -generator(),
+            true
+        );
+    }
+
+    #[test]
+    fn test_on_curve() {
+        assert_eq!(generator().is_on_curve(), true);
+        assert_eq!(generator().scalar(3usize).is_on_curve(), true);
+        assert_eq!(curve::Secp256k1CurveGroup::zero().is_on_curve(), true);
+        assert_eq!(
+            curve::Secp256k1CurveGroup::try_from((0u32, 0u32)).unwrap_err(),
+            Error::PointNotOnCurve
+        );
+        let gx = BigUint::from_slice(&curve::SECP256K1_GX);
+        let gy = BigUint::from_slice(&curve::SECP256K1_GY);
+        assert_eq!(
+            curve::Secp256k1CurveGroup::try_from((gx.clone(), gy.clone() + BigUint::from(1u32)))
+                .unwrap_err(),
+            Error::PointNotOnCurve
+        );
+        let off = ecg::from_biguint_unchecked::<ff::Secp256k1FieldEle, curve::Secp256k1Curve>(
+            gx,
+            gy + BigUint::from(1u32),
+        );
+        assert_eq!(off.is_on_curve(), false);
+    }
+
+    #[test]
+    fn test_try_from_rejects_malformed() {
+        let gy = "32670510020758816978083085130507043184471273380659243275938904335757337482424";
+        let p = "115792089237316195423570985008687907853269984665640564039457584007908834671663";
+        assert_eq!(
+            curve::Secp256k1CurveGroup::try_from((p, gy)).unwrap_err(),
+            Error::NonCanonicalEncoding
+        );
+        assert_eq!(curve::Secp256k1CurveGroup::try_from(("x", gy)).is_err(), true);
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        return (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect();
+    }
+
+    #[test]
+    fn test_sec1_encoding() {
+        let g_compressed = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let g_uncompressed = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                              483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+        let g7_compressed = "025cbdf0646e5db4eaa398f365f2ea7a0e3d419b7e0330e39ce92bddedcac4f9bc";
+        let g = generator();
+        assert_eq!(curve::Secp256k1Curve::to_sec1(&g, true), hex(g_compressed));
+        assert_eq!(curve::Secp256k1Curve::to_sec1(&g, false), hex(g_uncompressed));
+        assert_eq!(
+            curve::Secp256k1Curve::to_sec1(&generator().scalar(7), true),
+            hex(g7_compressed)
+        );
+        assert_eq!(curve::Secp256k1Curve::to_sec1(&(-g), true)[0], 0x03);
+        assert_eq!(
+            curve::Secp256k1Curve::to_sec1(&curve::Secp256k1CurveGroup::zero(), true),
+            vec![0x00]
+        );
+    }
+
+    #[test]
+    fn test_sec1_decoding() {
+        for k in 1usize..6 {
+            for p in vec![generator().scalar(k), -generator().scalar(k)] {
+                for compressed in vec![true, false] {
+                    let buf = curve::Secp256k1Curve::to_sec1(&p, compressed);
+                    assert_eq!(curve::Secp256k1Curve::from_sec1(&buf).unwrap() == p.clone(), true);
+                }
+            }
+        }
+        assert_eq!(curve::Secp256k1Curve::from_sec1(&[0x00]).unwrap().is_zero(), true);
+    }
+
+    #[test]
+    fn test_sec1_rejects_invalid() {
+        // x = 5 has no y on the curve
+        let mut buf = vec![0x02u8];
+        buf.extend(vec![0u8; 31]);
+        buf.push(5);
+        assert_eq!(
+            curve::Secp256k1Curve::from_sec1(&buf).unwrap_err(),
+            Error::PointNotOnCurve
+        );
+
+        let mut buf = curve::Secp256k1Curve::to_sec1(&generator(), false);
+        buf[64] ^= 1;
+        assert_eq!(
+            curve::Secp256k1Curve::from_sec1(&buf).unwrap_err(),
+            Error::PointNotOnCurve
+        );
+
+        let buf = curve::Secp256k1Curve::to_sec1(&generator(), true);
+        assert_eq!(
+            curve::Secp256k1Curve::from_sec1(&buf[..32]).unwrap_err(),
+            Error::InvalidLength {
+                expected: 33,
+                actual: 32
+            }
+        );
+        assert_eq!(
+            curve::Secp256k1Curve::from_sec1(&[0x00, 0x00]).unwrap_err(),
+            Error::InvalidLength {
+                expected: 1,
+                actual: 2
+            }
+        );
+        assert_eq!(
+            curve::Secp256k1Curve::from_sec1(&[0x05]).unwrap_err(),
+            Error::NonCanonicalEncoding
+        );
+
+        let mut buf = vec![0x02u8];
+        buf.extend(vec![0xffu8; 32]);
+        assert_eq!(
+            curve::Secp256k1Curve::from_sec1(&buf).unwrap_err(),
+            Error::NonCanonicalEncoding
+        );
+    }
+
+    #[test]
+    fn test_mul_ct() {
+        let n = BigUint::from_slice(&curve::SECP256K1_N);
+        let k = BigUint::parse_bytes(b"e7a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f", 16).unwrap();
+        for k in [k, BigUint::from(1u32), BigUint::from(2u32), n.clone() - 1u32].iter() {
+            let mut buf = [0u8; 32];
+            let le = k.to_bytes_le();
+            buf[..le.len()].copy_from_slice(&le);
+            assert_eq!(curve::Secp256k1Curve::mul_ct(&generator(), &buf) == generator().scalar(k.clone()), true);
+        }
+        let mut buf = [0u8; 32];
+        buf.copy_from_slice(&n.to_bytes_le());
+        assert_eq!(curve::Secp256k1Curve::mul_ct(&generator(), &buf).is_zero(), true);
+        assert_eq!(curve::Secp256k1Curve::mul_ct(&generator(), &[0u8; 32]).is_zero(), true);
+        assert_eq!(curve::Secp256k1Curve::mul_ct(&curve::Secp256k1CurveGroup::zero(), &buf).is_zero(), true);
+    }
+
+    #[test]
+    fn test_mul_generator() {
+        let n = BigUint::from_slice(&curve::SECP256K1_N);
+        let k = BigUint::parse_bytes(b"e7a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f", 16).unwrap();
+        assert_eq!(curve::Secp256k1Curve::mul_generator(&BigUint::from(1u32)) == generator(), true);
+        assert_eq!(curve::Secp256k1Curve::mul_generator(&k) == generator().scalar(k.clone()), true);
+        assert_eq!(curve::Secp256k1Curve::mul_generator(&n).is_zero(), true);
+        assert_eq!(curve::Secp256k1Curve::mul_generator(&(n.clone() - 1u32)) == -generator(), true);
+        assert_eq!(curve::Secp256k1Curve::mul_generator(&(n + 5u32)) == generator().scalar(5usize), true);
+    }
+
+    fn bench_scalar() -> BigUint {
+        return BigUint::from_slice(&curve::SECP256K1_N) - 1u32;
+    }
+
+    /// Against `bench_generator_scalar`
+    #[bench]
+    fn bench_mul_generator(bench: &mut Bencher) {
+        let k = bench_scalar();
+        curve::Secp256k1Curve::mul_generator(&k);
+        bench.iter(|| curve::Secp256k1Curve::mul_generator(&k));
+    }
+
+    #[bench]
+    fn bench_generator_scalar(bench: &mut Bencher) {
+        let g = generator();
+        let k = bench_scalar();
+        bench.iter(|| g.clone().scalar(k.clone()));
+    }
+
+    #[test]
+    fn test_msm() {
+        let n = BigUint::from_slice(&curve::SECP256K1_N);
+        let mut points = Vec::<curve::Secp256k1CurveGroup>::new();
+        let mut scalars = Vec::<BigUint>::new();
+        let mut p = generator();
+        for i in 0u32..70 {
+            points.push(p.clone());
+            scalars.push((n.clone() - BigUint::from(i) * 31337u32) >> (i as usize % 3));
+            p = p + generator();
+        }
+        points.push(curve::Secp256k1CurveGroup::zero());
+        scalars.push(BigUint::from(5u32));
+        for m in [0usize, 2, 71].iter() {
+            let e = points[..*m]
+                .iter()
+                .zip(scalars[..*m].iter())
+                .fold(curve::Secp256k1CurveGroup::zero(), |acc, (p, k)| acc + p.clone().scalar(k.clone()));
+            assert_eq!(ecg::msm(&points[..*m], &scalars[..*m]) == e, true);
+        }
+        // G - G
+        let s = [BigUint::from(1u32), n - 1u32];
+        assert_eq!(ecg::msm(&[generator(), generator()], &s).is_zero(), true);
+    }
+
+    #[test]
+    fn test_mul_scalar_field() {
+        let n = BigUint::from_slice(&curve::SECP256K1_N);
+        let a = BigUint::parse_bytes(b"e7a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f", 16).unwrap();
+        let b = n.clone() - 31337u32;
+        let (sa, sb) = (scalar::Secp256k1ScalarField::from(a.clone()), scalar::Secp256k1ScalarField::from(b.clone()));
+        let g = generator();
+        assert_eq!(g.clone() * sa.clone() == g.clone().scalar(a.clone()), true);
+        assert_eq!(g.clone() * (sa.clone() + sb.clone()) == g.clone() * sa.clone() + g.clone() * sb.clone(), true);
+        assert_eq!(g.clone() * (sa.clone() * sb.clone()) == (g.clone() * sa) * sb, true);
+        assert_eq!((g * scalar::Secp256k1ScalarField::from(n)).is_zero(), true);
+    }
+}
@@ -1,52 +1,23 @@
-use crate::algebra::fields::prime::BoxedPrimeField;
-use crate::algebra::fields::prime::FromBigUint;
-use crate::algebra::fields::prime::Property;
-use num_bigint::BigUint;
-use std::convert::TryFrom;
+use klefki_derive::prime_field;
 
-const SECP256K1_P : [u32; 8] = [
-    0xfffffc2fu32,
-    0xfffffffeu32,
-    0xffffffffu32,
-    0xffffffffu32,
-    0xffffffffu32,
-    0xffffffffu32,
-    0xffffffffu32,
-    0xffffffffu32,
-];
-
-#[derive(Debug, Eq, PartialEq, Clone)]
-struct Secp256k1FieldEle {
-    pub value: BigUint,
-}
-
-impl Property<Secp256k1FieldEle> for Secp256k1FieldEle {
-    fn prime(&self) -> BigUint {
-        BigUint::from_slice(&SECP256K1_P)
-    }
-    fn value(&self) -> BigUint {
-        return self.value.clone();
-    }
-}
-
-impl FromBigUint for Secp256k1FieldEle {
-    fn from(value: BigUint) -> BoxedPrimeField<Secp256k1FieldEle> {
-        return (box Self { value: value }) as BoxedPrimeField<Secp256k1FieldEle>;
-    }
-}
-
-
-type Secp256k1FinateField = Box<dyn Property<Secp256k1FieldEle>>;
+prime_field!(pub Secp256k1Field = "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algebra::traits::{CheckedInv, Sqrt};
+    use crate::Error;
+    use num_bigint::BigUint;
+    use crate::algebra::fields::prime::FromBigUint;
+    use rand::Rng;
+    use std::collections::HashSet;
+    use std::convert::TryFrom;
 
     #[test]
     fn ff_add_and_sub() {
-        let a = Secp256k1FinateField::from(1u16);
-        let b = Secp256k1FinateField::from(2u16);
-        let c = Secp256k1FinateField::from(3u16);
+        let a = Secp256k1Field::from(1u16);
+        let b = Secp256k1Field::from(2u16);
+        let c = Secp256k1Field::from(3u16);
         assert_eq!(a.clone() + b.clone() == c.clone(), true);
         assert_eq!(c.value(), BigUint::from(3u16));
         assert_eq!(c.clone() - a.clone() == b.clone(), true);
@@ -54,9 +25,9 @@ mod tests {
 
     #[test]
     fn ff_mul_and_div() {
-        let a = Secp256k1FinateField::from(1u16);
-        let b = Secp256k1FinateField::from(2u16);
-        let c = Secp256k1FinateField::from(2u16);
+        let a = Secp256k1Field::from(1u16);
+        let b = Secp256k1Field::from(2u16);
+        let c = Secp256k1Field::from(2u16);
         assert_eq!(a.clone() * b.clone() == c.clone(), true);
         assert_eq!(c.clone() / a.clone() == b.clone(), true);
     }
@@ -68,8 +39,61 @@ mod tests {
             3575560275, 1167457983, 588660917, 3001516614, 1119721974, 3807046053, 453375103,
             97425606,
         ];
-        let fa = Secp256k1FinateField::try_from(a).unwrap();
-        let fb = Secp256k1FinateField::from(&b[..]);
+        let fa = Secp256k1Field::try_from(a).unwrap();
+        let fb = Secp256k1Field::from(&b[..]);
         assert_eq!(fa == fb, true);
     }
+
+    #[test]
+    fn ff_sqrt() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..16 {
+            let x = Secp256k1Field::from(BigUint::from_slice(&rng.gen::<[u32; 8]>()));
+            let sq = x.clone() * x.clone();
+            assert_eq!(sq.is_square(), true);
+            let r = sq.sqrt().unwrap();
+            assert_eq!(r.clone() * r.clone() == sq, true);
+            assert_eq!(r == x.clone() || r == -x, true);
+        }
+    }
+
+    #[test]
+    fn ff_non_residue() {
+        let m = -Secp256k1Field::from(1u32);
+        assert_eq!(m.legendre(), -1);
+        assert_eq!(m.sqrt().is_none(), true);
+        assert_eq!(Secp256k1Field::from(0u32).sqrt().unwrap() == Secp256k1Field::from(0u32), true);
+    }
+
+    #[test]
+    fn ff_checked_inv() {
+        let a = Secp256k1Field::from(3u16);
+        let zero = Secp256k1Field::from(0u16);
+        assert_eq!(a.checked_inv().unwrap() * a.clone() == Secp256k1Field::from(1u16), true);
+        assert_eq!(zero.checked_inv().unwrap_err(), Error::NonInvertible);
+        assert_eq!(a.checked_div(&zero).unwrap_err(), Error::NonInvertible);
+        assert_eq!(Secp256k1Field::try_from("0x10").is_err(), true);
+    }
+
+    #[test]
+    fn ff_canonical() {
+        let p = Secp256k1Field::from(0u16).prime();
+        let a = Secp256k1Field::from(p.clone() + BigUint::from(1u16));
+        assert_eq!(a.value(), BigUint::from(1u16));
+        assert_eq!(a.clone() == Secp256k1Field::from(1u16), true);
+        assert_eq!((-Secp256k1Field::from(0u16)).value(), BigUint::from(0u16));
+
+        let mut set = HashSet::new();
+        set.insert(a);
+        assert_eq!(set.contains(&Secp256k1Field::from(1u16)), true);
+
+        assert_eq!(
+            Secp256k1FieldEle::from_canonical_biguint(p.clone()).unwrap_err(),
+            Error::NonCanonicalEncoding
+        );
+        assert_eq!(
+            Secp256k1FieldEle::from_canonical(p).unwrap_err(),
+            Error::NonCanonicalEncoding
+        );
+    }
 }
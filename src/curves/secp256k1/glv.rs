@@ -0,0 +1,204 @@
+/// DOC: Gallant, Lambert, Vanstone, Faster point multiplication on elliptic curves
+/// with efficient endomorphisms, CRYPTO 2001
+use crate::algebra::groups::arithmetic::straus_algorithm;
+use crate::algebra::groups::jacobian::JacobianPoint;
+use crate::algebra::groups::weierstrass::Summand;
+use crate::curves::secp256k1::curve::{Secp256k1Curve, Secp256k1CurveGroup, Secp256k1Params, SECP256K1_N};
+use crate::curves::secp256k1::ff;
+use num_bigint::{BigInt, BigUint, Sign};
+use std::sync::OnceLock;
+
+/// Cube root of unity β in F_p, φ(x, y) = (βx, y) = λ·(x, y)
+pub const SECP256K1_BETA: [u32; 8] = [
+    0x719501eeu32,
+    0xc1396c28u32,
+    0x12f58995u32,
+    0x9cf04975u32,
+    0xac3434e9u32,
+    0x6e64479eu32,
+    0x657c0710u32,
+    0x7ae96a2bu32,
+];
+
+/// Cube root of unity λ modulo n
+pub const SECP256K1_LAMBDA: [u32; 8] = [
+    0x1b23bd72u32,
+    0xdf02967cu32,
+    0x20816678u32,
+    0x122e22eau32,
+    0x8812645au32,
+    0xa5261c02u32,
+    0xc05c30e0u32,
+    0x5363ad4cu32,
+];
+
+/// Short basis (a1, b1), (a2, b2) of the lattice {(a, b) : a + b·λ ≡ 0 mod n},
+/// with b1 < 0 and b2 = a1.
+const GLV_A1: [u32; 4] = [0x9284eb15u32, 0xe86c90e4u32, 0xa7d46bcdu32, 0x3086d221u32];
+const GLV_MINUS_B1: [u32; 4] = [0x0abfe4c3u32, 0x6f547fa9u32, 0x010e8828u32, 0xe4437ed6u32];
+const GLV_A2: [u32; 5] = [0x9d44cfd8u32, 0x57c1108du32, 0xa8e2f3f6u32, 0x14ca50f7u32, 0x00000001u32];
+
+/// φ(x, y) = (βx, y), costs one field multiplication.
+pub fn endomorphism(p: &Secp256k1CurveGroup) -> Secp256k1CurveGroup {
+    return Secp256k1Curve::from_jacobian(&endomorphism_jacobian(&Secp256k1Curve::to_jacobian(p)));
+}
+
+/// β in Montgomery form, converted once.
+fn beta() -> ff::Secp256k1FieldEle {
+    static BETA: OnceLock<ff::Secp256k1FieldEle> = OnceLock::new();
+    return *BETA.get_or_init(|| ff::Secp256k1FieldEle::from(BigUint::from_slice(&SECP256K1_BETA)));
+}
+
+/// φ(X : Y : Z) = (βX : Y : Z)
+fn endomorphism_jacobian(p: &JacobianPoint<ff::Secp256k1FieldEle>) -> JacobianPoint<ff::Secp256k1FieldEle> {
+    return JacobianPoint {
+        x: p.x * beta(),
+        y: p.y,
+        z: p.z,
+    };
+}
+
+/// k ≡ k1 + k2·λ (mod n) with |k1|, |k2| < 2^128, by rounding k onto the
+/// short lattice basis.
+/// # ref:
+/// * Guide to Elliptic Curve Cryptography, Algorithm 3.74
+pub fn decompose(k: &BigUint) -> (BigInt, BigInt) {
+    let n = BigUint::from_slice(&SECP256K1_N);
+    let k = BigInt::from(k % n.clone());
+    let half = BigInt::from(n.clone() >> 1);
+    let n = BigInt::from(n);
+    let a1 = BigInt::from(BigUint::from_slice(&GLV_A1));
+    let b1 = -BigInt::from(BigUint::from_slice(&GLV_MINUS_B1));
+    let a2 = BigInt::from(BigUint::from_slice(&GLV_A2));
+    let b2 = a1.clone();
+
+    // c1 = round(b2·k / n), c2 = round(-b1·k / n), both numerators are positive
+    let c1 = (b2.clone() * k.clone() + half.clone()) / n.clone();
+    let c2 = (-b1.clone() * k.clone() + half) / n;
+    let k1 = k - c1.clone() * a1 - c2.clone() * a2;
+    let k2 = -c1 * b1 - c2 * b2;
+    return (k1, k2);
+}
+
+/// |k|·P', with P' = -P when k is negative.
+fn signed_term(p: JacobianPoint<ff::Secp256k1FieldEle>, k: BigInt) -> (JacobianPoint<ff::Secp256k1FieldEle>, BigUint) {
+    let (sign, k) = k.into_parts();
+    if sign == Sign::Minus {
+        return (JacobianPoint { x: p.x, y: -p.y, z: p.z }, k);
+    }
+    return (p, k);
+}
+
+/// Σ kᵢ·Pᵢ for the GLV split of every term, 2n half length scalars sharing
+/// one chain of doublings.
+fn split_msm(terms: &[(&Secp256k1CurveGroup, &BigUint)]) -> Secp256k1CurveGroup {
+    let a = Secp256k1Curve::a();
+    let mut points = Vec::<Summand<Secp256k1Params>>::with_capacity(2 * terms.len());
+    let mut scalars = Vec::<BigUint>::with_capacity(2 * terms.len());
+    for (p, k) in terms.iter() {
+        let p = Secp256k1Curve::to_jacobian(p);
+        let (k1, k2) = decompose(k);
+        for (q, k) in [signed_term(p, k1), signed_term(endomorphism_jacobian(&p), k2)].iter() {
+            points.push(Summand { p: *q, a: &a });
+            scalars.push(k.clone());
+        }
+    }
+    let init = Summand {
+        p: JacobianPoint::infinity(),
+        a: &a,
+    };
+    return Secp256k1Curve::from_jacobian(&straus_algorithm(&points, &scalars, init).p);
+}
+
+/// k·P as k1·P + k2·φ(P).
+pub fn mul(p: &Secp256k1CurveGroup, k: &BigUint) -> Secp256k1CurveGroup {
+    return split_msm(&[(p, k)]);
+}
+
+/// u1·P + u2·Q in one pass, e.g. u1·G + u2·Q for ECDSA verification,
+/// as a four term multi-scalar multiplication over 128 bit scalars.
+pub fn double_scalar_mul(
+    u1: &BigUint,
+    p: &Secp256k1CurveGroup,
+    u2: &BigUint,
+    q: &Secp256k1CurveGroup,
+) -> Secp256k1CurveGroup {
+    return split_msm(&[(p, u1), (q, u2)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algebra::groups::arithmetic::double_and_add_algorithm;
+    use crate::curves::secp256k1::curve;
+    use crate::curves::secp256k1::glv::*;
+    use num::Zero;
+    use std::convert::TryFrom;
+
+    fn generator() -> curve::Secp256k1CurveGroup {
+        return curve::Secp256k1CurveGroup::try_from((&curve::SECP256K1_GX[..], &curve::SECP256K1_GY[..]))
+            .unwrap();
+    }
+
+    fn scalars() -> Vec<BigUint> {
+        let n = BigUint::from_slice(&SECP256K1_N);
+        return vec![
+            BigUint::zero(),
+            BigUint::from(1u32),
+            BigUint::from(2u32),
+            BigUint::parse_bytes(b"e7a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f", 16).unwrap(),
+            BigUint::parse_bytes(b"3086d221a7d46bcde86c90e49284eb15", 16).unwrap(),
+            BigUint::from_slice(&SECP256K1_LAMBDA),
+            n.clone() - 1u32,
+            n.clone(),
+            n << 3,
+        ];
+    }
+
+    #[test]
+    fn test_endomorphism() {
+        let g = generator();
+        let lambda = BigUint::from_slice(&SECP256K1_LAMBDA);
+        let expected = double_and_add_algorithm(lambda, g.clone(), curve::Secp256k1CurveGroup::zero());
+        assert_eq!(endomorphism(&g) == expected, true);
+        assert_eq!(endomorphism(&curve::Secp256k1CurveGroup::zero()).is_zero(), true);
+    }
+
+    #[test]
+    fn test_decompose() {
+        let n = BigInt::from(BigUint::from_slice(&SECP256K1_N));
+        let lambda = BigInt::from(BigUint::from_slice(&SECP256K1_LAMBDA));
+        for k in scalars().iter() {
+            let (k1, k2) = decompose(k);
+            assert_eq!(k1.magnitude().bits() <= 128 && k2.magnitude().bits() <= 128, true);
+            let r = ((k1 + k2 * lambda.clone() - BigInt::from(k.clone())) % n.clone() + n.clone()) % n.clone();
+            assert_eq!(r.is_zero(), true);
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        let g = generator();
+        let p = double_and_add_algorithm(BigUint::from(7919u32), g.clone(), curve::Secp256k1CurveGroup::zero());
+        for k in scalars().iter() {
+            let expected = double_and_add_algorithm(k.clone(), p.clone(), curve::Secp256k1CurveGroup::zero());
+            assert_eq!(mul(&p, k) == expected, true);
+        }
+        assert_eq!(mul(&curve::Secp256k1CurveGroup::zero(), &BigUint::from(5u32)).is_zero(), true);
+    }
+
+    #[test]
+    fn test_double_scalar_mul() {
+        let g = generator();
+        let q = double_and_add_algorithm(BigUint::from(104729u32), g.clone(), curve::Secp256k1CurveGroup::zero());
+        let ks = scalars();
+        for (u1, u2) in ks.iter().zip(ks.iter().rev()) {
+            let expected = double_and_add_algorithm(u1.clone(), g.clone(), curve::Secp256k1CurveGroup::zero())
+                + double_and_add_algorithm(u2.clone(), q.clone(), curve::Secp256k1CurveGroup::zero());
+            assert_eq!(double_scalar_mul(u1, &g, u2, &q) == expected, true);
+        }
+        // u·G - u·G
+        let n = BigUint::from_slice(&SECP256K1_N);
+        let u = ks[3].clone();
+        assert_eq!(double_scalar_mul(&u, &g, &(n - u.clone()), &g).is_zero(), true);
+    }
+}
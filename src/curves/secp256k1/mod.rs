@@ -1,2 +1,4 @@
 pub mod ff;
 pub mod curve;
+pub mod glv;
+pub mod scalar;
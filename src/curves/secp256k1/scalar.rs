@@ -0,0 +1,24 @@
+use klefki_derive::prime_field;
+
+// Integers modulo the group order n, for private keys, ECDSA nonces and
+// other exponents. `P * k` runs `mul_ct` on `to_bytes_le`.
+prime_field!(
+    pub Secp256k1ScalarField = "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    generator = 7,
+    factors = [107361793816595537, 174723607534414371449, 341948486974166000522343609283189],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::secp256k1::curve;
+    use num::Zero;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_modulus() {
+        let n = BigUint::from_slice(&curve::SECP256K1_N);
+        assert_eq!(Secp256k1ScalarField::from(0u32).prime(), n.clone());
+        assert_eq!(Secp256k1ScalarField::from(n).is_zero(), true);
+    }
+}
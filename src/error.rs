@@ -0,0 +1,45 @@
+use num_bigint::ParseBigIntError;
+use std::fmt;
+
+/// Errors surfaced when handling untrusted field elements and curve points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The element has no multiplicative inverse, i.e. it is zero.
+    NonInvertible,
+    /// The encoding is not the canonical representative, e.g. a value >= p.
+    NonCanonicalEncoding,
+    /// The encoding has the wrong number of bytes.
+    InvalidLength { expected: usize, actual: usize },
+    /// The string is not a well formed hexadecimal encoding.
+    InvalidHex,
+    /// The coordinates do not satisfy the curve equation.
+    PointNotOnCurve,
+    /// The point is on the curve but outside of the prime order subgroup.
+    NotInSubgroup,
+    /// The input could not be parsed as an integer.
+    Parse(ParseBigIntError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Error::NonInvertible => write!(f, "element is not invertible"),
+            Error::NonCanonicalEncoding => write!(f, "encoding is not canonical"),
+            Error::InvalidLength { expected, actual } => {
+                write!(f, "invalid length: expected {} bytes, got {}", expected, actual)
+            }
+            Error::InvalidHex => write!(f, "invalid hex encoding"),
+            Error::PointNotOnCurve => write!(f, "point is not on the curve"),
+            Error::NotInSubgroup => write!(f, "point is not in the prime order subgroup"),
+            Error::Parse(e) => write!(f, "parse error: {}", e),
+        };
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseBigIntError> for Error {
+    fn from(e: ParseBigIntError) -> Self {
+        return Error::Parse(e);
+    }
+}
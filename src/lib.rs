@@ -1,8 +1,23 @@
+//! # Constant time
+//! Only the following are meant for secret data: the arithmetic of
+//! `algebra::fields::fp::Fp` together with `ConditionalSwap` and
+//! `Fp::to_bytes_le`, `ExtendedArithmetic::mul_ct`, `BabyJubJubCurve::mul_ct`,
+//! `Secp256k1Curve::mul_ct` and the `Mul` of curve points by the scalar
+//! field elements, which calls them. Everything built on `BigUint`, `Scalar`
+//! and `Mul<BigUint>` for curve points, and the other encodings, branch on
+//! their inputs.
 #![feature(trait_alias)]
 #![feature(box_syntax)]
 #![feature(test)]
 #![feature(destructuring_assignment)]
 #![feature(trivial_bounds)]
 
+// `prime_field!` expands to `::klefki::...` paths, which must resolve here too
+extern crate self as klefki;
+
 pub mod algebra;
 pub mod curves;
+pub mod error;
+
+pub use error::Error;
+pub use klefki_derive::prime_field;